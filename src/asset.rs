@@ -1,25 +1,228 @@
 use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
-use bevy::asset::{AssetLoader, BoxedFuture, LoadedAsset};
+use anyhow::{anyhow, Context};
+use bevy::asset::{AssetIo, AssetLoader, BoxedFuture, LoadState, LoadedAsset};
 use bevy::prelude::*;
 use bevy::reflect::TypeUuid;
 use bevy::render::texture::{CompressedImageFormats, ImageType};
-use dotent::asset::Asset;
-use dotent::entry::Entry;
+use bevy::tasks::{IoTaskPool, Task};
+use flate2::read::GzDecoder;
+use futures_lite::future;
+use tar::Archive;
 
-use crate::{AppState, EntryFileData, PROJECT_FILE};
+use crate::{
+    loading::{LoadError, LoadingProgress},
+    text_box::TextBox,
+    AppState, EntryFileData, GitteolConfig,
+};
+
+/// Pictures decoded per frame while loading, so the loading screen can show progress.
+const IMAGES_PER_FRAME: usize = 4;
 
 #[derive(TypeUuid)]
 #[uuid = "eaa5b97c-a72e-4cd4-9c29-2708e58fbd61"]
-pub struct EntryProject(pub dotent::project::Project);
+pub struct EntryProject {
+    pub project: dotent::project::Project,
+    /// Text of the `textBox` objects, by object id.
+    pub(crate) text_boxes: HashMap<String, TextBox>,
+    /// `project.json` as it was read, with the fields dotent doesn't know about.
+    pub(crate) json: serde_json::Value,
+}
+
+/// Parses `project.json`, keeping what dotent can't read about text objects.
+///
+/// Entry writes them as `textBox` and puts their text into the entity, while dotent expects
+/// `textbox` and drops unknown fields.
+fn parse_project(data: &[u8]) -> anyhow::Result<EntryProject> {
+    let mut json: serde_json::Value = serde_json::from_slice(data)?;
+    let original = json.clone();
+    let mut text_boxes = HashMap::new();
+    if let Some(objects) = json
+        .get_mut("objects")
+        .and_then(serde_json::Value::as_array_mut)
+    {
+        for object in objects {
+            if object["objectType"] != "textBox" {
+                continue;
+            }
+            object["objectType"] = "textbox".into();
+            if !object["selectedPictureId"].is_string() {
+                object["selectedPictureId"] = "".into();
+            }
+            let id = object["id"].as_str().unwrap_or_default().to_string();
+            text_boxes.insert(id, serde_json::from_value(object["entity"].clone())?);
+        }
+    }
+    Ok(EntryProject {
+        project: serde_json::from_value(json)?,
+        text_boxes,
+        json: original,
+    })
+}
+
+pub(crate) struct ImageData {
+    pub(crate) name: String,
+    pub(crate) ext: String,
+    pub(crate) data: Vec<u8>,
+}
+
+/// A project with the pictures it needs, or why it couldn't be read.
+type ReadProject = anyhow::Result<(EntryProject, Vec<ImageData>)>;
 
+/// Reads the project and images from a `.ent` file, a gzipped tarball.
+pub(crate) fn read_entry(bytes: &[u8]) -> ReadProject {
+    let mut project = None;
+    let mut images = Vec::new();
+
+    let mut archive = Archive::new(GzDecoder::new(bytes));
+    for entry in archive.entries().context("not an Entry project archive")? {
+        let mut entry = entry.context("not an Entry project archive")?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        if path == "temp/project.json" {
+            let mut data = Vec::new();
+            entry
+                .read_to_end(&mut data)
+                .context("broken Entry project archive")?;
+            project = Some(parse_project(&data).context("invalid project.json")?);
+        } else if let Some((name, ext)) = image_path(&path) {
+            let mut data = Vec::new();
+            entry
+                .read_to_end(&mut data)
+                .context("broken Entry project archive")?;
+            images.push(ImageData {
+                name: name.to_string(),
+                ext: ext.to_string(),
+                data,
+            });
+        }
+        // TODO: load sounds
+    }
+
+    let project = project.ok_or_else(|| anyhow!("no project.json in the Entry project archive"))?;
+    Ok((project, images))
+}
+
+/// Reads a bare `project.json`, such as one from an unpacked `.ent` or from Entry's API.
+///
+/// It isn't loaded as an asset, so that apps embedding the plugin keep their own `.json` assets.
+/// Pictures are looked up relative to the JSON file and to its parent directory, since an
+/// unpacked `.ent` keeps both `project.json` and the pictures' `temp/` paths in `temp/`.
+async fn read_project_json(asset_io: &dyn AssetIo, path: &Path) -> ReadProject {
+    let bytes = asset_io.load_path(path).await?;
+    let project = parse_project(&bytes).context("invalid project.json")?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut images: Vec<ImageData> = Vec::new();
+    let pictures = project
+        .project
+        .objects
+        .iter()
+        .flat_map(|object| &object.sprite.pictures);
+    for picture in pictures {
+        let Some(name) = &picture.filename else {
+            continue;
+        };
+        if images.iter().any(|image| &image.name == name) {
+            continue;
+        }
+        let mut found = None;
+        for path in picture_paths(dir, &picture.fileurl) {
+            if let Ok(data) = asset_io.load_path(&path).await {
+                found = Some((path, data));
+                break;
+            }
+        }
+        let Some((path, data)) = found else {
+            warn!("cannot find picture {}", picture.fileurl);
+            continue;
+        };
+        let ext = match path.extension() {
+            Some(ext) => ext.to_string_lossy().into_owned(),
+            None => picture.image_type.clone(),
+        };
+        images.push(ImageData {
+            name: name.clone(),
+            ext,
+            data,
+        });
+    }
+
+    Ok((project, images))
+}
+
+fn picture_paths(dir: &Path, fileurl: &str) -> Vec<PathBuf> {
+    let fileurl = fileurl.trim_start_matches('/');
+    let mut paths = vec![dir.join(fileurl)];
+    if let Some(parent) = dir.parent() {
+        paths.push(parent.join(fileurl));
+    }
+    paths
+}
+
+/// Where a `.ent` keeps an image: under the first four characters of its name, like Entry does.
+/// Shorter names are padded with `_` so that [`image_path`] still reads them back.
+pub(crate) fn image_archive_path(name: &str, ext: &str) -> String {
+    let hash: Vec<char> = format!("{:_<4}", name).chars().take(4).collect();
+    let (a, b): (String, String) = (hash[..2].iter().collect(), hash[2..].iter().collect());
+    format!("temp/{}/{}/image/{}.{}", a, b, name, ext)
+}
+
+/// Splits `temp/ab/cd/image/<name>.<ext>` into the name and extension of the image.
+fn image_path(path: &str) -> Option<(&str, &str)> {
+    match path.split('/').collect::<Vec<_>>()[..] {
+        ["temp", _, _, "image", file] => file.split_once('.'),
+        _ => None,
+    }
+}
+
+/// A loaded `.ent` or `project.json` file. The project of a `.ent` is its `project` labeled
+/// asset.
+///
+/// Loading never fails so that the error can be shown to the user; see [`LoadError`].
 #[derive(TypeUuid, Default)]
 #[uuid = "3a54cf64-1848-4944-bac7-1caa59ccf92c"]
 pub struct EntryFile {
-    pub project: Handle<EntryProject>,
-    pub images: HashMap<String, Handle<Image>>,
+    /// Pictures which are not decoded yet, by file name.
+    pub(crate) images: Vec<ImageData>,
+    /// The original `.ent` archive, or `None` for a `project.json`.
+    pub(crate) archive: Option<Vec<u8>>,
+    pub(crate) error: Option<String>,
 }
 
+/// Decoded pictures of the project, by file name.
+#[derive(Resource, Default)]
+pub(crate) struct ProjectImages(pub(crate) HashMap<String, Handle<Image>>);
+
+impl EntryFile {
+    /// Splits a read project into the file and the project, keeping the error to show it.
+    fn new(result: ReadProject, archive: Option<Vec<u8>>) -> (Self, Option<EntryProject>) {
+        match result {
+            Ok((project, images)) => (
+                EntryFile {
+                    images,
+                    archive,
+                    error: None,
+                },
+                Some(project),
+            ),
+            Err(err) => (
+                EntryFile {
+                    images: Vec::new(),
+                    archive: None,
+                    error: Some(format!("{:#}", err)),
+                },
+                None,
+            ),
+        }
+    }
+}
+
+/// A `project.json` being read; see [`read_project_json`].
+#[derive(Resource, Default)]
+pub(crate) struct ProjectJsonTask(Option<Task<ReadProject>>);
+
 #[derive(Default)]
 pub struct EntryAssetLoader;
 
@@ -30,36 +233,12 @@ impl AssetLoader for EntryAssetLoader {
         load_context: &'a mut bevy::asset::LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
         Box::pin(async move {
-            let entry = Entry::read(bytes)?;
-            let project = EntryProject(entry.project().clone());
-
-            let project_handle = load_context
-                .set_labeled_asset::<EntryProject>("project", LoadedAsset::new(project));
-
-            let assets = entry.assets();
-            let mut images_map = HashMap::new();
-            for (key, asset) in assets {
-                match asset {
-                    Asset::Image(image_data) => {
-                        // https://github.com/bevyengine/bevy/blob/b027d402e29d7d4f26062051161bc2187f168e80/crates/bevy_render/src/texture/image_texture_loader.rs#L49-L58
-                        let dyn_img = Image::from_buffer(
-                            image_data.data(),
-                            ImageType::Extension(image_data.ext()),
-                            CompressedImageFormats::default(),
-                            true,
-                        )?;
-                        let handle =
-                            load_context.set_labeled_asset::<Image>(key, LoadedAsset::new(dyn_img));
-                        images_map.insert(key.to_string(), handle);
-                    }
-                    Asset::Sound(_) => continue, // TODO: impl this
-                }
+            let (entry_file, project) = EntryFile::new(read_entry(bytes), Some(bytes.to_vec()));
+            if let Some(project) = project {
+                load_context
+                    .set_labeled_asset::<EntryProject>("project", LoadedAsset::new(project));
             }
-
-            load_context.set_default_asset(LoadedAsset::new(EntryFile {
-                project: project_handle,
-                images: images_map,
-            }));
+            load_context.set_default_asset(LoadedAsset::new(entry_file));
 
             Ok(())
         })
@@ -70,17 +249,239 @@ impl AssetLoader for EntryAssetLoader {
     }
 }
 
-pub(crate) fn setup_asset(asset_server: Res<AssetServer>, mut entry_file: ResMut<EntryFileData>) {
-    entry_file.handle = asset_server.load(PROJECT_FILE);
+pub(crate) fn setup_asset(
+    asset_server: Res<AssetServer>,
+    config: Res<GitteolConfig>,
+    mut entry_file: ResMut<EntryFileData>,
+    mut json_task: ResMut<ProjectJsonTask>,
+    mut project_images: ResMut<ProjectImages>,
+    mut progress: ResMut<LoadingProgress>,
+) {
+    entry_file.path = resolve_project_path(asset_server.asset_io(), &config.project_path);
+    if entry_file.path.ends_with(".json") {
+        let asset_server = asset_server.clone();
+        let path = PathBuf::from(&entry_file.path);
+        let task = async move { read_project_json(asset_server.asset_io(), &path).await };
+        json_task.0 = Some(IoTaskPool::get().spawn(task));
+        entry_file.handle = Handle::default();
+        entry_file.project = Handle::default();
+    } else {
+        entry_file.handle = asset_server.load(entry_file.path.as_str());
+        entry_file.project = asset_server.load(format!("{}#project", entry_file.path));
+    }
+    project_images.0.clear();
+    *progress = LoadingProgress::default();
 }
 
+/// A directory is an unpacked `.ent`, with its project in `temp/project.json` or `project.json`.
+fn resolve_project_path(asset_io: &dyn AssetIo, path: &str) -> String {
+    let dir = Path::new(path);
+    if !asset_io.is_dir(dir) {
+        return path.to_string();
+    }
+    let project = ["temp/project.json", "project.json"]
+        .iter()
+        .map(|file| dir.join(file))
+        .find(|path| asset_io.get_metadata(path).is_ok())
+        .unwrap_or_else(|| dir.join("project.json"));
+    project.to_string_lossy().into_owned()
+}
+
+/// Decodes the project's pictures a few at a time, then starts the project.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn check_asset_loading(
-    entry_file_assets: Res<Assets<EntryFile>>,
-    entry_file_data: Res<EntryFileData>,
+    asset_server: Res<AssetServer>,
+    mut entry_file_assets: ResMut<Assets<EntryFile>>,
+    mut projects: ResMut<Assets<EntryProject>>,
+    mut entry_file_data: ResMut<EntryFileData>,
+    mut json_task: ResMut<ProjectJsonTask>,
+    mut images: ResMut<Assets<Image>>,
+    mut project_images: ResMut<ProjectImages>,
+    mut progress: ResMut<LoadingProgress>,
+    mut load_error: ResMut<LoadError>,
     mut state: ResMut<State<AppState>>,
 ) {
-    let entry_file = entry_file_assets.get(&entry_file_data.handle);
-    if entry_file.is_some() {
-        state.set(AppState::MainApp).unwrap();
+    let mut fail = |message: String| {
+        load_error.0 = message;
+        state.set(AppState::Error).unwrap();
+    };
+
+    if let Some(task) = &mut json_task.0 {
+        let Some(result) = future::block_on(future::poll_once(task)) else {
+            return;
+        };
+        json_task.0 = None;
+        let (entry_file, project) = EntryFile::new(result, None);
+        if let Some(project) = project {
+            entry_file_data.project = projects.add(project);
+        }
+        entry_file_data.handle = entry_file_assets.add(entry_file);
+    }
+
+    if asset_server.get_load_state(&entry_file_data.handle) == LoadState::Failed {
+        return fail(format!("cannot read {}", entry_file_data.path));
+    }
+    let Some(entry_file) = entry_file_assets.get(&entry_file_data.handle) else {
+        return;
     };
+    if let Some(error) = &entry_file.error {
+        return fail(format!("cannot load {}: {}", entry_file_data.path, error));
+    }
+
+    progress.total = entry_file.images.len();
+    for image in entry_file
+        .images
+        .iter()
+        .skip(progress.decoded)
+        .take(IMAGES_PER_FRAME)
+    {
+        // https://github.com/bevyengine/bevy/blob/b027d402e29d7d4f26062051161bc2187f168e80/crates/bevy_render/src/texture/image_texture_loader.rs#L49-L58
+        let decoded = Image::from_buffer(
+            &image.data,
+            ImageType::Extension(&image.ext),
+            CompressedImageFormats::default(),
+            true,
+        );
+        match decoded {
+            Ok(decoded) => {
+                let handle = images.add(decoded);
+                project_images.0.insert(image.name.clone(), handle);
+                progress.decoded += 1;
+            }
+            Err(err) => {
+                return fail(format!(
+                    "cannot decode picture {}.{}: {}",
+                    image.name, image.ext, err
+                ));
+            }
+        }
+    }
+
+    if progress.decoded == progress.total {
+        state.set(AppState::MainApp).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_bundled_project() {
+        let bytes = std::fs::read("assets/project.ent").unwrap();
+        let (project, images) = read_entry(&bytes).unwrap();
+        assert_eq!(project.project.objects.len(), 2);
+        assert!(project.text_boxes.is_empty());
+        assert!(!images.is_empty());
+    }
+
+    #[test]
+    fn reads_unpacked_project_json() {
+        let dir = std::env::temp_dir().join(format!("gitteol-unpacked-{}", std::process::id()));
+        let bytes = std::fs::read("assets/project.ent").unwrap();
+        Archive::new(GzDecoder::new(&bytes[..]))
+            .unpack(&dir)
+            .unwrap();
+
+        let asset_io = bevy::asset::FileAssetIo::new(&dir, false);
+        let path = Path::new("temp/project.json");
+        let (project, images) = future::block_on(read_project_json(&asset_io, path)).unwrap();
+        let (expected, expected_images) = read_entry(&bytes).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(project.json, expected.json);
+        assert_eq!(images.len(), expected_images.len());
+    }
+
+    #[test]
+    fn only_ent_files_use_the_asset_loader() {
+        assert_eq!(EntryAssetLoader.extensions(), ["ent"]);
+    }
+
+    #[test]
+    fn reports_bad_archives() {
+        let err = read_entry(b"not a tarball").err().unwrap();
+        assert_eq!(format!("{}", err), "not an Entry project archive");
+    }
+
+    #[test]
+    fn picture_paths_try_the_unpacked_root() {
+        assert_eq!(
+            picture_paths(Path::new("game/temp"), "temp/ab/cd/image/ab.png"),
+            vec![
+                PathBuf::from("game/temp/temp/ab/cd/image/ab.png"),
+                PathBuf::from("game/temp/ab/cd/image/ab.png"),
+            ]
+        );
+        assert_eq!(
+            picture_paths(Path::new(""), "/uploads/ab/ab.png"),
+            vec![PathBuf::from("uploads/ab/ab.png")]
+        );
+    }
+
+    #[test]
+    fn image_archive_paths_read_back() {
+        assert_eq!(
+            image_archive_path("0hf5cuqy", "png"),
+            "temp/0h/f5/image/0hf5cuqy.png"
+        );
+        for name in ["0hf5cuqy", "a", "abc", "그림"] {
+            assert_eq!(
+                image_path(&image_archive_path(name, "png")),
+                Some((name, "png"))
+            );
+        }
+    }
+
+    #[test]
+    fn image_path_matches_only_images() {
+        assert_eq!(
+            image_path("temp/0h/f5/image/0hf5cuqy.png"),
+            Some(("0hf5cuqy", "png"))
+        );
+        assert_eq!(image_path("temp/0h/f5/thumb/0hf5cuqy.png"), None);
+        assert_eq!(image_path("temp/project.json"), None);
+    }
+
+    #[test]
+    fn parses_text_box_objects() {
+        let json = r##"{
+            "objects": [{
+                "id": "t1",
+                "name": "text",
+                "script": "[]",
+                "objectType": "textBox",
+                "rotateMethod": "free",
+                "scene": "s1",
+                "sprite": { "pictures": [], "sounds": [] },
+                "selectedPictureId": null,
+                "lock": false,
+                "entity": {
+                    "x": 0, "y": 0, "regX": 0, "regY": 0, "scaleX": 1, "scaleY": 1,
+                    "rotation": 0, "direction": 90, "width": 100, "height": 20,
+                    "font": "bold 30px NanumGothic", "visible": true,
+                    "text": "hello", "colour": "#ff0000", "bgColor": "transparent",
+                    "lineBreak": true, "underLine": true, "strike": false
+                }
+            }],
+            "scenes": [{ "id": "s1", "name": "scene" }],
+            "variables": [],
+            "messages": [],
+            "functions": [],
+            "speed": 60,
+            "interface": { "canvasWidth": 480, "menuWidth": 280, "object": "t1" },
+            "name": "text project"
+        }"##;
+        let project = parse_project(json.as_bytes()).unwrap();
+        assert!(matches!(
+            project.project.objects[0].object_type,
+            dotent::project::object::ObjectType::TextBox
+        ));
+
+        let text_box = &project.text_boxes["t1"];
+        assert_eq!(text_box.text, "hello");
+        assert_eq!(text_box.font_size(), 30.0);
+        assert!(text_box.is_bold());
+        assert!(text_box.line_break && text_box.under_line && !text_box.strike);
+    }
 }
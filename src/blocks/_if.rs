@@ -3,22 +3,20 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, parse_statements, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Label, Value};
 
 #[derive(Clone)]
 pub(crate) struct If {
     id: Id,
     condition: Value,
-    statements_length: usize,
+    end: Label,
 }
 impl Block for If {
     fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
         let condition = self
             .condition
             .take_raw_value(memory)
-            .unwrap()
-            .as_bool()
-            .unwrap();
+            .is_some_and(|value| value.is_truthy());
         if condition {
             BlockReturn {
                 pointer: pointer + 1,
@@ -27,7 +25,7 @@ impl Block for If {
             }
         } else {
             BlockReturn {
-                pointer: pointer + self.statements_length + 1,
+                pointer: self.end.pointer(),
                 is_continue: false,
                 return_value: None,
             }
@@ -37,23 +35,61 @@ impl Block for If {
     fn get_id(&self) -> &Id {
         &self.id
     }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.end]
+    }
 }
 impl If {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
-        let (condition, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-        let mut statements = parse_statements(&block.statements);
-        let statement_len = statements[0].len();
-        blocks.push(
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(condition) = compiler.required_param(block, 0) else {
+            return;
+        };
+        let end = compiler.label();
+        compiler.push(
             If {
                 id: block.id.clone().into(),
                 condition,
-                statements_length: statement_len,
+                end,
             }
             .into(),
         );
-        blocks.append(&mut statements[0]);
-        blocks
+        compiler.statements(&block.statements, 0);
+        compiler.bind(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::{
+        test_world::{change_variable, literal, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn runs_its_statements_only_when_true() {
+        let mut world = TestWorld::new();
+        let [yes, no, after] =
+            ["yes", "no", "after"].map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let script = [
+            raw_block(
+                "_if",
+                vec![literal(true)],
+                vec![vec![change_variable("yes")]],
+            ),
+            raw_block(
+                "_if",
+                vec![literal(false)],
+                vec![vec![change_variable("no")]],
+            ),
+            change_variable("after"),
+        ];
+        world.spawn_code(Compiler::compile(&script));
+
+        world.tick();
+        assert_eq!(world.number(yes), 1.0);
+        assert_eq!(world.number(no), 0.0);
+        assert_eq!(world.number(after), 1.0);
+        assert_eq!(world.runners(), 0);
     }
 }
@@ -0,0 +1,99 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct BooleanAndOr {
+    id: Id,
+    left: Option<Value>,
+    op: String,
+    right: Option<Value>,
+}
+impl Block for BooleanAndOr {
+    fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        // Both sides are taken so that their return values don't linger in memory.
+        let left = truthy(&self.left, memory);
+        let right = truthy(&self.right, memory);
+
+        let result = match &self.op[..] {
+            "AND" => left && right,
+            "OR" => left || right,
+            _ => unreachable!(),
+        };
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::Bool(result)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl BooleanAndOr {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let left = compiler.param(&block.params[0]);
+
+        let Some(op) = compiler.required_param(block, 1) else {
+            return;
+        };
+        let op = op.as_string().unwrap();
+
+        let right = compiler.param(&block.params[2]);
+
+        compiler.push(
+            BooleanAndOr {
+                id: block.id.clone().into(),
+                left,
+                op,
+                right,
+            }
+            .into(),
+        );
+    }
+}
+
+/// An empty boolean slot counts as false.
+pub(super) fn truthy(value: &Option<Value>, memory: &mut Memory) -> bool {
+    value
+        .as_ref()
+        .and_then(|value| value.take_raw_value(memory))
+        .is_some_and(|value| value.is_truthy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn combine(left: Option<bool>, op: &str, right: Option<bool>) -> bool {
+        let block = BooleanAndOr {
+            id: Id::from_str("combine"),
+            left: left.map(Value::Bool),
+            op: op.to_string(),
+            right: right.map(Value::Bool),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.is_truthy()
+    }
+
+    #[test]
+    fn combines_both_sides() {
+        assert!(combine(Some(true), "AND", Some(true)));
+        assert!(!combine(Some(true), "AND", Some(false)));
+        assert!(combine(Some(false), "OR", Some(true)));
+        assert!(!combine(Some(false), "OR", Some(false)));
+    }
+
+    #[test]
+    fn empty_sides_are_false() {
+        assert!(!combine(None, "AND", Some(true)));
+        assert!(combine(None, "OR", Some(true)));
+        assert!(!combine(None, "OR", None));
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct BooleanBasicOperator {
@@ -46,19 +46,21 @@ impl Block for BooleanBasicOperator {
     }
 }
 impl BooleanBasicOperator {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
-
-        let (left, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(left) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (op, _) = parse_param(&block.params[1]).unwrap();
+        let Some(op) = compiler.required_param(block, 1) else {
+            return;
+        };
         let op = op.as_string().unwrap();
 
-        let (right, mut param_blocks) = parse_param(&block.params[2]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(right) = compiler.required_param(block, 2) else {
+            return;
+        };
 
-        blocks.push(
+        compiler.push(
             BooleanBasicOperator {
                 id: block.id.clone().into(),
                 left,
@@ -67,6 +69,49 @@ impl BooleanBasicOperator {
             }
             .into(),
         );
-        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn compare(left: Value, op: &str, right: Value) -> bool {
+        let block = BooleanBasicOperator {
+            id: Id::from_str("compare"),
+            left,
+            op: op.to_string(),
+            right,
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.is_truthy()
+    }
+
+    fn text(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    #[test]
+    fn compares_numbers() {
+        assert!(compare(Value::Number(1.0), "LESS", Value::Number(2.0)));
+        assert!(compare(
+            Value::Number(2.0),
+            "GREATER_OR_EQUAL",
+            Value::Number(2.0)
+        ));
+        assert!(!compare(
+            Value::Number(2.0),
+            "NOT_EQUAL",
+            Value::Number(2.0)
+        ));
+        assert!(compare(text("10"), "EQUAL", Value::Number(10.0)));
+    }
+
+    #[test]
+    fn compares_text_for_equality_only() {
+        assert!(compare(text("엔트리"), "EQUAL", text("엔트리")));
+        assert!(compare(text("a"), "NOT_EQUAL", text("b")));
+        assert!(!compare(text("b"), "GREATER", text("a")));
     }
 }
@@ -0,0 +1,63 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{boolean_and_or::truthy, compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct BooleanNot {
+    id: Id,
+    value: Option<Value>,
+}
+impl Block for BooleanNot {
+    fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        let result = !truthy(&self.value, memory);
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::Bool(result)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl BooleanNot {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        // params[0] and params[2] are the text around the slot.
+        let value = compiler.param(&block.params[1]);
+
+        compiler.push(
+            BooleanNot {
+                id: block.id.clone().into(),
+                value,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn not(value: Option<Value>) -> bool {
+        let block = BooleanNot {
+            id: Id::from_str("not"),
+            value,
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.is_truthy()
+    }
+
+    #[test]
+    fn negates_its_value() {
+        assert!(!not(Some(Value::Bool(true))));
+        assert!(not(Some(Value::Bool(false))));
+        assert!(not(None));
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct CalcBasic {
@@ -51,19 +51,21 @@ impl Block for CalcBasic {
     }
 }
 impl CalcBasic {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
-
-        let (left, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(left) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (op, _) = parse_param(&block.params[1]).unwrap();
+        let Some(op) = compiler.required_param(block, 1) else {
+            return;
+        };
         let op = op.as_string().unwrap();
 
-        let (right, mut param_blocks) = parse_param(&block.params[2]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(right) = compiler.required_param(block, 2) else {
+            return;
+        };
 
-        blocks.push(
+        compiler.push(
             CalcBasic {
                 id: block.id.clone().into(),
                 left,
@@ -72,6 +74,41 @@ impl CalcBasic {
             }
             .into(),
         );
-        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn calc(left: Value, op: &str, right: Value) -> Value {
+        let block = CalcBasic {
+            id: Id::from_str("calc"),
+            left,
+            op: op.to_string(),
+            right,
+        };
+        TestWorld::new().run(&block, 0).return_value.unwrap()
+    }
+
+    #[test]
+    fn minus_and_multi() {
+        let result = calc(Value::Number(1.5), "MINUS", Value::Number(4.0));
+        assert_eq!(result.as_number().unwrap(), -2.5);
+        let result = calc(Value::String("3".to_string()), "MULTI", Value::Number(-2.0));
+        assert_eq!(result.as_number().unwrap(), -6.0);
+    }
+
+    #[test]
+    fn divide_by_zero_is_infinite() {
+        let result = calc(Value::Number(1.0), "DIVIDE", Value::Number(0.0));
+        assert_eq!(result.as_number().unwrap(), f32::INFINITY);
+    }
+
+    #[test]
+    fn plus_joins_text() {
+        let result = calc(Value::String("a".to_string()), "PLUS", Value::Number(1.0));
+        assert_eq!(result.as_string().unwrap(), "a1");
     }
 }
@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct CalcOperation {
@@ -10,16 +10,17 @@ pub(crate) struct CalcOperation {
 }
 
 impl CalcOperation {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
-
-        let (value, mut param_blocks) = parse_param(&block.params[1]).unwrap();
-        blocks.append(&mut param_blocks);
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
 
-        let (operator, _) = parse_param(&block.params[3]).unwrap();
+        let Some(operator) = compiler.required_param(block, 3) else {
+            return;
+        };
         let operator = operator.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             CalcOperation {
                 id: block.id.clone().into(),
                 value,
@@ -27,8 +28,44 @@ impl CalcOperation {
             }
             .into(),
         );
+    }
+}
+
+fn factorial(value: f32) -> f32 {
+    if value < 0.0 || value.fract() != 0.0 {
+        return f32::NAN;
+    }
+    (1..=value as u32).map(|n| n as f32).product()
+}
 
-        blocks
+fn calculate(operator: &str, value: f32) -> f32 {
+    match operator {
+        "square" => value.powi(2),
+        "root" => value.sqrt(),
+        "sin" => value.to_radians().sin(),
+        "cos" => value.to_radians().cos(),
+        "tan" => value.to_radians().tan(),
+        // Entry names the inverse functions `*_radian`, but they return degrees.
+        "asin_radian" => value.asin().to_degrees(),
+        "acos_radian" => value.acos().to_degrees(),
+        "atan_radian" => value.atan().to_degrees(),
+        "log" => value.log10(),
+        "ln" => value.ln(),
+        "unnatural" => {
+            let unnatural = value - value.floor();
+            if value < 0.0 {
+                1.0 - unnatural
+            } else {
+                unnatural
+            }
+        }
+        "floor" => value.floor(),
+        "ceil" => value.ceil(),
+        // JavaScript's `Math.round` rounds halves up, also below zero.
+        "round" => (value + 0.5).floor(),
+        "factorial" => factorial(value),
+        "abs" => value.abs(),
+        _ => unreachable!(),
     }
 }
 
@@ -46,32 +83,7 @@ impl Block for CalcOperation {
             .as_number()
             .unwrap();
 
-        let result = match &self.operator[..] {
-            "square" => value.powi(2),
-            "root" => value.sqrt(),
-            "sin" => value.to_radians().sin(),
-            "cos" => value.to_radians().cos(),
-            "tan" => value.to_radians().tan(),
-            "asin_radian" => value.asin().to_degrees(),
-            "acos_radian" => value.acos().to_degrees(),
-            "atan_radian" => value.atan().to_degrees(),
-            "log" => value.log10(),
-            "ln" => value.ln(),
-            "unnatural" => {
-                let unnatural = value - value.round();
-                if value < 0.0 {
-                    1.0 - unnatural
-                } else {
-                    unnatural
-                }
-            }
-            "floor" => value.floor(),
-            "ceil" => value.ceil(),
-            "round" => value.round(),
-            "factorial" => value.fract(),
-            "abs" => value.abs(),
-            _ => unreachable!(),
-        };
+        let result = calculate(&self.operator, value);
 
         BlockReturn {
             pointer: pointer + 1,
@@ -84,3 +96,58 @@ impl Block for CalcOperation {
         &self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::calculate;
+
+    fn assert_close(left: f32, right: f32) {
+        assert!((left - right).abs() < 1e-4, "{} != {}", left, right);
+    }
+
+    #[test]
+    fn square_and_root() {
+        assert_close(calculate("square", 3.0), 9.0);
+        assert_close(calculate("root", 16.0), 4.0);
+    }
+
+    #[test]
+    fn trigonometry_uses_degrees() {
+        assert_close(calculate("sin", 90.0), 1.0);
+        assert_close(calculate("cos", 180.0), -1.0);
+        assert_close(calculate("tan", 45.0), 1.0);
+        assert_close(calculate("asin_radian", 1.0), 90.0);
+        assert_close(calculate("acos_radian", 0.0), 90.0);
+        assert_close(calculate("atan_radian", 1.0), 45.0);
+    }
+
+    #[test]
+    fn logarithms() {
+        assert_close(calculate("log", 1000.0), 3.0);
+        assert_close(calculate("ln", std::f32::consts::E), 1.0);
+    }
+
+    #[test]
+    fn unnatural() {
+        assert_close(calculate("unnatural", 1.25), 0.25);
+        assert_close(calculate("unnatural", -1.25), 0.25);
+    }
+
+    #[test]
+    fn rounding() {
+        assert_close(calculate("floor", -1.5), -2.0);
+        assert_close(calculate("ceil", 1.2), 2.0);
+        assert_close(calculate("round", 2.5), 3.0);
+        assert_close(calculate("round", -2.5), -2.0);
+        assert_close(calculate("round", -2.6), -3.0);
+        assert_close(calculate("abs", -4.0), 4.0);
+    }
+
+    #[test]
+    fn factorial() {
+        assert_close(calculate("factorial", 0.0), 1.0);
+        assert_close(calculate("factorial", 5.0), 120.0);
+        assert!(calculate("factorial", -1.0).is_nan());
+        assert!(calculate("factorial", 1.5).is_nan());
+    }
+}
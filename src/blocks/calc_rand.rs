@@ -0,0 +1,134 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct CalcRand {
+    id: Id,
+    left: Value,
+    right: Value,
+}
+
+impl CalcRand {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(left) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(right) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        compiler.push(
+            CalcRand {
+                id: block.id.clone().into(),
+                left,
+                right,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for CalcRand {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let left = self.left.take_raw_value(memory);
+        let right = self.right.take_raw_value(memory);
+
+        let is_float = [&left, &right].iter().any(|value| {
+            value
+                .as_ref()
+                .and_then(|value| value.as_string().ok())
+                .is_some_and(|value| value.contains('.'))
+        });
+
+        // Entry reads empty or non-numeric text as 0.
+        let number = |value: Option<Value>| {
+            value.map_or(0.0, |value| value.as_number().unwrap_or(0.0)) as f64
+        };
+        let left = number(left);
+        let right = number(right);
+        let (min, max) = (left.min(right), left.max(right));
+
+        let result = if is_float {
+            Value::String(format!("{:.2}", ctx.random.next() * (max - min) + min))
+        } else {
+            Value::Number((ctx.random.next() * (max - min + 1.0) + min).floor() as f32)
+        };
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn rand(left: &str, right: &str) -> CalcRand {
+        CalcRand {
+            id: Id::from_str("rand"),
+            left: Value::String(left.to_string()),
+            right: Value::String(right.to_string()),
+        }
+    }
+
+    fn results(world: &mut TestWorld, block: &CalcRand) -> Vec<String> {
+        (0..100)
+            .map(|_| {
+                let value = world.run(block, 0).return_value.unwrap();
+                value.as_string().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn integers_between_integers() {
+        let mut world = TestWorld::new();
+        for value in results(&mut world, &rand("10", "1")) {
+            let value: f32 = value.parse().unwrap();
+            assert_eq!(value.fract(), 0.0);
+            assert!((1.0..=10.0).contains(&value), "{}", value);
+        }
+    }
+
+    #[test]
+    fn decimals_when_either_side_has_a_point() {
+        let mut world = TestWorld::new();
+        let values = results(&mut world, &rand("1.0", "2"));
+        for value in &values {
+            assert_eq!(value.split('.').nth(1).map(str::len), Some(2), "{}", value);
+            let value: f32 = value.parse().unwrap();
+            assert!((1.0..=2.0).contains(&value), "{}", value);
+        }
+        assert!(values.iter().any(|value| !value.ends_with(".00")));
+    }
+
+    #[test]
+    fn empty_or_text_fields_are_zero() {
+        let mut world = TestWorld::new();
+        for value in results(&mut world, &rand("", "abc")) {
+            assert_eq!(value, "0");
+        }
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let (mut a, mut b) = (TestWorld::new(), TestWorld::new());
+        let block = rand("1", "1000");
+        assert_eq!(results(&mut a, &block), results(&mut b, &block));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    code::{Context, Control, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct CallFunction {
+    id: Id,
+    function: Id,
+    args: Vec<Option<Value>>,
+}
+impl Block for CallFunction {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.as_ref().and_then(|arg| arg.take_raw_value(memory)))
+            .collect();
+
+        ctx.control.push(Control::Call {
+            function: self.function.clone(),
+            args,
+        });
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl CallFunction {
+    pub(crate) fn build(
+        block: &dotent::project::script::Block,
+        function: &str,
+        compiler: &mut Compiler,
+    ) {
+        let mut args = Vec::new();
+        for param in &block.params {
+            args.push(compiler.param(param));
+        }
+
+        compiler.push(
+            CallFunction {
+                id: block.id.clone().into(),
+                function: Id::from_str(function),
+                args,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn requests_the_call_with_its_arguments() {
+        let mut world = TestWorld::new();
+        let block = CallFunction {
+            id: Id::from_str("call"),
+            function: Id::from_str("f"),
+            args: vec![Some(Value::Number(1.0)), None],
+        };
+
+        assert_eq!(world.run(&block, 4).pointer, 5);
+        let [Control::Call { function, args }] = &world.control[..] else {
+            panic!("expected one call, got {:?}", world.control);
+        };
+        assert_eq!(function, &Id::from_str("f"));
+        assert_eq!(args[0].as_ref().unwrap().as_number(), Ok(1.0));
+        assert!(args[1].is_none());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct ChangeStringCase {
+    id: Id,
+    value: Value,
+    case: String,
+}
+
+impl ChangeStringCase {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(case) = compiler.required_param(block, 3) else {
+            return;
+        };
+        let case = case.as_string().unwrap();
+
+        compiler.push(
+            ChangeStringCase {
+                id: block.id.clone().into(),
+                value,
+                case,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for ChangeStringCase {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self
+            .value
+            .take_raw_value(memory)
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        let result = match &self.case[..] {
+            "toUpperCase" => value.to_uppercase(),
+            "toLowerCase" => value.to_lowercase(),
+            _ => unreachable!(),
+        };
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::String(result)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn change(value: &str, case: &str) -> String {
+        let block = ChangeStringCase {
+            id: Id::from_str("case"),
+            value: Value::String(value.to_string()),
+            case: case.to_string(),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.as_string().unwrap()
+    }
+
+    #[test]
+    fn changes_case() {
+        assert_eq!(change("Entry 엔트리", "toUpperCase"), "ENTRY 엔트리");
+        assert_eq!(change("Entry 엔트리", "toLowerCase"), "entry 엔트리");
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct ChangeVariable {
@@ -36,18 +36,18 @@ impl Block for ChangeVariable {
     }
 }
 impl ChangeVariable {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(variable_id) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (variable_id, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        let (value, mut param_blocks) = parse_param(&block.params[1]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
 
         let variable_id = variable_id.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             ChangeVariable {
                 id: block.id.clone().into(),
                 variable_id,
@@ -55,6 +55,35 @@ impl ChangeVariable {
             }
             .into(),
         );
-        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn change(value: Value, by: Value) -> Value {
+        let mut world = TestWorld::new();
+        let variable = world.spawn_variable("v", value);
+        let block = ChangeVariable {
+            id: Id::from_str("change"),
+            variable_id: "v".to_string(),
+            value: by,
+        };
+        world.run(&block, 0);
+        world.variable(variable).value.clone()
+    }
+
+    #[test]
+    fn adds_numbers() {
+        let value = change(Value::Number(1.5), Value::Number(2.0));
+        assert_eq!(value.as_number().unwrap(), 3.5);
+    }
+
+    #[test]
+    fn appends_to_text() {
+        let value = change(Value::String("a".to_string()), Value::Number(2.0));
+        assert_eq!(value.as_string().unwrap(), "a2");
     }
 }
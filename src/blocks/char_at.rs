@@ -0,0 +1,107 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct CharAt {
+    id: Id,
+    value: Value,
+    index: Value,
+}
+
+impl CharAt {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(index) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        compiler.push(
+            CharAt {
+                id: block.id.clone().into(),
+                value,
+                index,
+            }
+            .into(),
+        );
+    }
+}
+
+fn char_at(value: &str, index: f32) -> String {
+    if index < 0.0 {
+        return String::new();
+    }
+    value
+        .chars()
+        .nth(index as usize)
+        .map(String::from)
+        .unwrap_or_default()
+}
+
+impl Block for CharAt {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self.value.take_raw_value(memory).unwrap();
+        let index = self.index.take_raw_value(memory).unwrap();
+
+        let value = value.as_string().unwrap();
+        // Like in Entry, a non-numeric index reads as 0, which is out of range.
+        let index = index.as_number().unwrap_or(0.0) - 1.0;
+
+        let result = Value::String(char_at(&value, index));
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn run(value: &str, index: Value) -> String {
+        let block = CharAt {
+            id: Id::from_str("char_at"),
+            value: Value::String(value.to_string()),
+            index,
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.as_string().unwrap()
+    }
+
+    #[test]
+    fn numeric_text_is_an_index() {
+        assert_eq!(run("엔트리", Value::String("2".to_string())), "트");
+    }
+
+    #[test]
+    fn non_numeric_index_is_empty() {
+        assert_eq!(run("엔트리", Value::String("abc".to_string())), "");
+    }
+
+    #[test]
+    fn char_at_is_zero_based_after_conversion() {
+        assert_eq!(char_at("엔트리", 0.0), "엔");
+        assert_eq!(char_at("엔트리", 2.0), "리");
+    }
+
+    #[test]
+    fn char_at_out_of_range_is_empty() {
+        assert_eq!(char_at("abc", -1.0), "");
+        assert_eq!(char_at("abc", 3.0), "");
+    }
+}
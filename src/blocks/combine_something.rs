@@ -0,0 +1,83 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct CombineSomething {
+    id: Id,
+    left: Value,
+    right: Value,
+}
+
+impl CombineSomething {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(left) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(right) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        compiler.push(
+            CombineSomething {
+                id: block.id.clone().into(),
+                left,
+                right,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for CombineSomething {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let left = self.left.take_raw_value(memory).unwrap();
+        let right = self.right.take_raw_value(memory).unwrap();
+
+        let result = Value::String(format!(
+            "{}{}",
+            left.as_string().unwrap(),
+            right.as_string().unwrap()
+        ));
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn combine(left: Value, right: Value) -> String {
+        let block = CombineSomething {
+            id: Id::from_str("combine"),
+            left,
+            right,
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.as_string().unwrap()
+    }
+
+    #[test]
+    fn joins_text_and_numbers() {
+        assert_eq!(
+            combine(Value::String("엔트리".to_string()), Value::Number(3.0)),
+            "엔트리3"
+        );
+        assert_eq!(combine(Value::Number(1.5), Value::Bool(true)), "1.5true");
+    }
+}
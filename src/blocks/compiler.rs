@@ -0,0 +1,314 @@
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use dotent::project::script::{Block as RawBlock, Param, Script};
+
+use crate::common::Id;
+
+use super::{
+    call_function::CallFunction, function_param::FunctionParam, Block, BlockEnum, BlockType,
+    BlockVec, LiteralBlockType, Value,
+};
+
+/// A jump target inside a compiled [`BlockVec`], resolved by [`Compiler::finish`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Label {
+    index: usize,
+    pointer: usize,
+}
+impl Label {
+    pub(crate) fn pointer(&self) -> usize {
+        self.pointer
+    }
+}
+
+/// The innermost loop being compiled, used by `stop_repeat` and `continue_repeat`.
+#[derive(Clone)]
+pub(crate) struct LoopFrame {
+    pub(crate) id: Id,
+    pub(crate) memory: &'static [&'static str],
+    pub(crate) next: Label,
+    pub(crate) end: Label,
+}
+
+/// Lowers Entry's nested scripts into a flat [`BlockVec`].
+#[derive(Default)]
+pub(crate) struct Compiler {
+    blocks: BlockVec,
+    labels: Vec<Option<usize>>,
+    loops: Vec<LoopFrame>,
+    /// Set when the block being built lacks a param it needs, so that it is dropped.
+    missing_param: bool,
+}
+
+impl Compiler {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn compile(raw_blocks: &[RawBlock]) -> BlockVec {
+        let mut compiler = Compiler::new();
+        for raw_block in raw_blocks {
+            compiler.block(raw_block);
+        }
+        compiler.finish()
+    }
+
+    /// Creates a new label which is not yet bound to any position.
+    pub(crate) fn label(&mut self) -> Label {
+        self.labels.push(None);
+        Label {
+            index: self.labels.len() - 1,
+            pointer: 0,
+        }
+    }
+
+    /// Binds the label to the position of the next pushed block.
+    pub(crate) fn bind(&mut self, label: Label) {
+        self.labels[label.index] = Some(self.blocks.len());
+    }
+
+    pub(crate) fn begin_loop(&mut self, frame: LoopFrame) {
+        self.loops.push(frame);
+    }
+
+    pub(crate) fn end_loop(&mut self) {
+        self.loops.pop();
+    }
+
+    pub(crate) fn current_loop(&self) -> Option<&LoopFrame> {
+        self.loops.last()
+    }
+
+    pub(crate) fn push(&mut self, block: BlockEnum) {
+        self.blocks.push(block);
+    }
+
+    fn build(&mut self, raw_block: &RawBlock) -> bool {
+        let len = self.blocks.len();
+        let missing_param = std::mem::replace(&mut self.missing_param, false);
+
+        let block_type = &raw_block.block_type;
+        let built = if let Ok(block_type) = BlockType::from_str(block_type) {
+            block_type.build(raw_block, self);
+            true
+        } else if let Some(function) = block_type.strip_prefix("func_") {
+            CallFunction::build(raw_block, function, self);
+            true
+        } else if block_type.starts_with("stringParam_") || block_type.starts_with("booleanParam_")
+        {
+            FunctionParam::build(raw_block, self);
+            true
+        } else {
+            false
+        };
+
+        // Drops whatever the block pushed before finding out it cannot run, such as its params.
+        if self.missing_param {
+            warn!("skipping block with a missing param: {}", block_type);
+            self.blocks.truncate(len);
+        }
+        self.missing_param = missing_param;
+        built
+    }
+
+    pub(crate) fn block(&mut self, raw_block: &RawBlock) {
+        if !self.build(raw_block) {
+            warn!("unsupported block: {}", raw_block.block_type);
+        }
+    }
+
+    /// Compiles the `index`-th statement list of a block, if it exists.
+    pub(crate) fn statements(&mut self, script: &Script, index: usize) {
+        if let Some(code) = script.0.get(index) {
+            for raw_block in code {
+                self.block(raw_block);
+            }
+        }
+    }
+
+    pub(crate) fn param(&mut self, param: &Param) -> Option<Value> {
+        let val = match param {
+            Param::Block(block) => {
+                let len = self.blocks.len();
+                if self.build(block) {
+                    if self.blocks.len() == len {
+                        warn!("block did not produce a value: {}", block.block_type);
+                        return None;
+                    }
+                    let last_id = self.blocks.last()?.get_id().clone();
+                    Value::Memory((last_id, "return_value".to_string()))
+                } else if let Ok(literal) = LiteralBlockType::from_str(&block.block_type) {
+                    match literal {
+                        LiteralBlockType::True => Value::Bool(true),
+                        LiteralBlockType::False => Value::Bool(false),
+                        _ => return self.param(block.params.first()?),
+                    }
+                } else {
+                    warn!("unsupported block: {}", block.block_type);
+                    return None;
+                }
+            }
+            Param::Number(val) => Value::Number(*val),
+            Param::String(val) => Value::String(val.to_string()),
+            Param::Bool(val) => Value::Bool(*val),
+            Param::Null => return None,
+        };
+        Some(val)
+    }
+
+    /// Compiles the `index`-th param of a block which cannot run without it. When the param is
+    /// empty or has no value, the block is dropped and the builder should return early.
+    pub(crate) fn required_param(&mut self, block: &RawBlock, index: usize) -> Option<Value> {
+        let value = block.params.get(index).and_then(|param| self.param(param));
+        if value.is_none() {
+            self.missing_param = true;
+        }
+        value
+    }
+
+    /// Resolves every label used by the compiled blocks into an absolute pointer.
+    pub(crate) fn finish(mut self) -> BlockVec {
+        for block in &mut self.blocks {
+            for label in block.labels_mut() {
+                label.pointer = self.labels[label.index].expect("unbound label");
+            }
+        }
+        self.blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::{change_variable, compare_variable, literal, raw_block};
+
+    /// The pointers each compiled block jumps to.
+    fn targets(raw_blocks: &[RawBlock]) -> Vec<Vec<usize>> {
+        Compiler::compile(raw_blocks)
+            .iter_mut()
+            .map(|block| block.labels_mut().iter().map(|l| l.pointer()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn labels_resolve_to_where_they_are_bound() {
+        let mut compiler = Compiler::new();
+        let before = compiler.label();
+        let after = compiler.label();
+        compiler.bind(before);
+        compiler.block(&change_variable("n"));
+        compiler.block(&change_variable("n"));
+        compiler.bind(after);
+        assert_eq!(compiler.labels, [Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn branches_jump_past_their_statements() {
+        let if_block = raw_block(
+            "_if",
+            vec![literal(true)],
+            vec![vec![change_variable("n"), change_variable("n")]],
+        );
+        // _if, change, change, change
+        assert_eq!(
+            targets(&[if_block, change_variable("n")]),
+            [vec![3], vec![], vec![], vec![]]
+        );
+
+        let if_else = raw_block(
+            "if_else",
+            vec![literal(true)],
+            vec![vec![change_variable("a")], vec![change_variable("b")]],
+        );
+        // if_else, change a, if_else_end, change b
+        assert_eq!(targets(&[if_else]), [vec![3], vec![], vec![4], vec![]]);
+    }
+
+    #[test]
+    fn loops_jump_back_to_their_condition() {
+        let stop = raw_block("stop_repeat", vec![], vec![]);
+        let next = raw_block("continue_repeat", vec![], vec![]);
+        let repeat = raw_block(
+            "repeat_while_true",
+            vec![
+                compare_variable("n", "LESS", 3.0),
+                Param::String("while".to_string()),
+            ],
+            vec![vec![stop, next]],
+        );
+        // get_variable, boolean_basic_operator, repeat_while_true, stop_repeat,
+        // continue_repeat, loop_end
+        assert_eq!(
+            targets(&[repeat]),
+            [vec![], vec![], vec![6], vec![6], vec![5], vec![0]]
+        );
+    }
+
+    #[test]
+    fn nested_loops_keep_their_own_labels() {
+        let inner = raw_block(
+            "repeat_basic",
+            vec![Param::Number(2.0)],
+            vec![vec![raw_block("stop_repeat", vec![], vec![])]],
+        );
+        let outer = raw_block(
+            "repeat_inf",
+            vec![],
+            vec![vec![inner, raw_block("stop_repeat", vec![], vec![])]],
+        );
+        // repeat_inf, repeat_basic, stop_repeat, loop_end, stop_repeat, loop_end
+        assert_eq!(
+            targets(&[outer]),
+            [vec![], vec![4], vec![4], vec![1], vec![6], vec![0]]
+        );
+    }
+
+    #[test]
+    fn blocks_made_by_the_compiler_cannot_be_parsed() {
+        for block_type in ["loop_end", "if_else_end", "call_function", "function_param"] {
+            assert!(BlockType::from_str(block_type).is_err());
+            assert!(Compiler::compile(&[raw_block(block_type, vec![], vec![])]).is_empty());
+        }
+    }
+
+    #[test]
+    fn unsupported_param_has_no_value() {
+        let mut compiler = Compiler::new();
+        let param = Param::Block(raw_block("no_such_block", vec![], vec![]));
+        assert!(compiler.param(&param).is_none());
+        assert!(compiler.finish().is_empty());
+    }
+
+    #[test]
+    fn blocks_missing_a_param_are_skipped() {
+        let empty_if = raw_block("_if", vec![Param::Null], vec![vec![change_variable("n")]]);
+        let blocks = Compiler::compile(&[empty_if, change_variable("n")]);
+        assert_eq!(blocks.len(), 1);
+
+        let unsupported = Param::Block(raw_block("no_such_block", vec![], vec![]));
+        let repeat = raw_block("repeat_basic", vec![unsupported], vec![vec![]]);
+        assert!(Compiler::compile(&[repeat]).is_empty());
+
+        let compare = raw_block("boolean_basic_operator", vec![], vec![]);
+        assert!(
+            Compiler::compile(&[raw_block("_if", vec![Param::Block(compare)], vec![])]).is_empty()
+        );
+    }
+
+    #[test]
+    fn only_the_block_missing_a_param_is_skipped() {
+        let set = raw_block(
+            "set_variable",
+            vec![Param::String("n".to_string()), Param::Null],
+            vec![],
+        );
+        let if_block = raw_block(
+            "_if",
+            vec![literal(true)],
+            vec![vec![set, change_variable("n")]],
+        );
+        // _if, change
+        assert_eq!(targets(&[if_block]), [vec![2], vec![]]);
+    }
+}
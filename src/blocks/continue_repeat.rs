@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Label};
+
+#[derive(Clone)]
+pub(crate) struct ContinueRepeat {
+    id: Id,
+    next: Label,
+}
+impl Block for ContinueRepeat {
+    fn run(&self, _pointer: usize, _memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        BlockReturn {
+            pointer: self.next.pointer(),
+            is_continue: false,
+            return_value: None,
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.next]
+    }
+}
+impl ContinueRepeat {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(frame) = compiler.current_loop().cloned() else {
+            warn!("continue_repeat outside of a loop: {}", block.id.as_str());
+            return;
+        };
+
+        compiler.push(
+            ContinueRepeat {
+                id: block.id.clone().into(),
+                next: frame.next,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use crate::blocks::{
+        test_world::{change_variable, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn skips_the_rest_of_the_iteration() {
+        let mut world = TestWorld::new();
+        let [n, skipped, after] =
+            ["n", "skipped", "after"].map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let repeat = raw_block(
+            "repeat_basic",
+            vec![Param::Number(3.0)],
+            vec![vec![
+                change_variable("n"),
+                raw_block("continue_repeat", vec![], vec![]),
+                change_variable("skipped"),
+            ]],
+        );
+        world.spawn_code(Compiler::compile(&[repeat, change_variable("after")]));
+
+        for _ in 0..10 {
+            world.tick();
+        }
+        assert_eq!(world.number(n), 3.0);
+        assert_eq!(world.number(skipped), 0.0);
+        assert_eq!(world.number(after), 1.0);
+        assert_eq!(world.runners(), 0);
+    }
+
+    #[test]
+    fn is_dropped_outside_of_a_loop() {
+        let blocks = Compiler::compile(&[raw_block("continue_repeat", vec![], vec![])]);
+        assert!(blocks.is_empty());
+    }
+}
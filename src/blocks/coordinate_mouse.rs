@@ -0,0 +1,73 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct CoordinateMouse {
+    id: Id,
+    coordinate: String,
+}
+
+impl CoordinateMouse {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(coordinate) = compiler.required_param(block, 1) else {
+            return;
+        };
+        let coordinate = coordinate.as_string().unwrap();
+
+        compiler.push(
+            CoordinateMouse {
+                id: block.id.clone().into(),
+                coordinate,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for CoordinateMouse {
+    fn run(
+        &self,
+        pointer: usize,
+        _memory: &mut crate::code::Memory,
+        ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let result = match &self.coordinate[..] {
+            "x" => ctx.mouse.pos.x,
+            "y" => ctx.mouse.pos.y,
+            _ => unreachable!(),
+        };
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::Number(result)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn reads_the_mouse_in_stage_coordinates() {
+        let mut world = TestWorld::new();
+        world.set_mouse(Vec2::new(-120.0, 45.0));
+        for (coordinate, expected) in [("x", -120.0), ("y", 45.0)] {
+            let block = CoordinateMouse {
+                id: Id::from_str("mouse"),
+                coordinate: coordinate.to_string(),
+            };
+            let result = world.run(&block, 0).return_value.unwrap();
+            assert_eq!(result.as_number(), Ok(expected));
+        }
+    }
+}
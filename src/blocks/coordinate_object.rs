@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct CoordinateObject {
@@ -10,16 +10,18 @@ pub(crate) struct CoordinateObject {
 }
 
 impl CoordinateObject {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
-
-        let (target, _) = parse_param(&block.params[1]).unwrap();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(target) = compiler.required_param(block, 1) else {
+            return;
+        };
         let target = target.as_string().unwrap();
 
-        let (coordinate, _) = parse_param(&block.params[3]).unwrap();
+        let Some(coordinate) = compiler.required_param(block, 3) else {
+            return;
+        };
         let coordinate = coordinate.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             CoordinateObject {
                 id: block.id.clone().into(),
                 target,
@@ -27,8 +29,6 @@ impl CoordinateObject {
             }
             .into(),
         );
-
-        blocks
     }
 }
 
@@ -0,0 +1,80 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct CountMatchString {
+    id: Id,
+    value: Value,
+    target: Value,
+}
+
+impl CountMatchString {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(target) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        compiler.push(
+            CountMatchString {
+                id: block.id.clone().into(),
+                value,
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+fn count_matches(value: &str, target: &str) -> usize {
+    if target.is_empty() {
+        return value.chars().count() + 1;
+    }
+    value.matches(target).count()
+}
+
+impl Block for CountMatchString {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self.value.take_raw_value(memory).unwrap();
+        let target = self.target.take_raw_value(memory).unwrap();
+
+        let value = value.as_string().unwrap();
+        let target = target.as_string().unwrap();
+
+        let result = Value::Number(count_matches(&value, &target) as f32);
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_matches;
+
+    #[test]
+    fn count_matches_does_not_overlap() {
+        assert_eq!(count_matches("aaaa", "aa"), 2);
+        assert_eq!(count_matches("abc", "d"), 0);
+    }
+
+    #[test]
+    fn count_matches_empty_target() {
+        assert_eq!(count_matches("abc", ""), 4);
+    }
+}
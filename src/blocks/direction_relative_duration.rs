@@ -0,0 +1,83 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, timed::animate, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct DirectionRelativeDuration {
+    id: Id,
+    duration: Value,
+    amount: Value,
+}
+impl Block for DirectionRelativeDuration {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let duration = memory
+            .cache(&self.id, "duration", &self.duration)
+            .as_number()
+            .unwrap();
+        let amount = memory
+            .cache(&self.id, "amount", &self.amount)
+            .as_number()
+            .unwrap();
+
+        animate(
+            &self.id,
+            pointer,
+            memory,
+            ctx,
+            duration,
+            &["duration", "amount"],
+            |share, ctx| {
+                let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+                this.direction = (this.direction + share * amount).rem_euclid(360.0);
+            },
+        )
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl DirectionRelativeDuration {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(duration) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        let Some(amount) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        compiler.push(
+            DirectionRelativeDuration {
+                id: block.id.clone().into(),
+                duration,
+                amount,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn turns_without_rotating_the_picture() {
+        let mut world = TestWorld::new();
+        world.step = 0.5;
+        let block = DirectionRelativeDuration {
+            id: Id::from_str("turn"),
+            duration: Value::Number(1.0),
+            amount: Value::Number(300.0),
+        };
+
+        assert_eq!(world.run_timed(&block), 2);
+        assert_eq!(world.this().direction, 30.0);
+        assert_eq!(world.this().rotation, 0.0);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct DistanceSomething {
+    id: Id,
+    target: String,
+}
+
+impl DistanceSomething {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(target) = compiler.required_param(block, 1) else {
+            return;
+        };
+        let target = target.as_string().unwrap();
+
+        compiler.push(
+            DistanceSomething {
+                id: block.id.clone().into(),
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for DistanceSomething {
+    fn run(
+        &self,
+        pointer: usize,
+        _memory: &mut crate::code::Memory,
+        ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let target = match &self.target[..] {
+            "mouse" => ctx.mouse.pos,
+            _ => {
+                let id = Id::from_str(&self.target);
+                let entity = ctx.ids.get(&id).unwrap();
+                let target = ctx.objects.get(*entity).unwrap();
+                target.translation.truncate()
+            }
+        };
+
+        let this = ctx.objects.get(*ctx.owner).unwrap();
+        let result = this.translation.truncate().distance(target);
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::Number(result)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn distance(world: &mut TestWorld, target: &str) -> Value {
+        let block = DistanceSomething {
+            id: Id::from_str("distance"),
+            target: target.to_string(),
+        };
+        world.run(&block, 0).return_value.unwrap()
+    }
+
+    #[test]
+    fn measures_to_the_mouse_and_objects() {
+        let mut world = TestWorld::new();
+        world.set_mouse(Vec2::new(30.0, -40.0));
+        world.spawn_object("other", Vec2::new(0.0, 12.0));
+        assert_eq!(distance(&mut world, "mouse").as_number(), Ok(50.0));
+        assert_eq!(distance(&mut world, "other").as_number(), Ok(12.0));
+    }
+}
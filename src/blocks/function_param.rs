@@ -0,0 +1,77 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct FunctionParam {
+    id: Id,
+    name: String,
+}
+impl Block for FunctionParam {
+    fn run(&self, pointer: usize, _memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        // Arguments left out of the call are empty, as in Entry.
+        let value = ctx.params.get(&self.name).cloned().unwrap_or_else(|| {
+            if self.name.starts_with("booleanParam_") {
+                Value::Bool(false)
+            } else {
+                Value::String(String::new())
+            }
+        });
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(value),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl FunctionParam {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        compiler.push(
+            FunctionParam {
+                id: block.id.clone().into(),
+                name: block.block_type.clone(),
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn param(name: &str) -> FunctionParam {
+        FunctionParam {
+            id: Id::from_str("param"),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_the_argument() {
+        let mut world = TestWorld::new();
+        world
+            .params
+            .insert("stringParam_a".to_string(), Value::Number(3.0));
+        let value = world.run(&param("stringParam_a"), 0).return_value.unwrap();
+        assert_eq!(value.as_number(), Ok(3.0));
+    }
+
+    #[test]
+    fn missing_arguments_are_empty() {
+        let mut world = TestWorld::new();
+        let value = world.run(&param("stringParam_a"), 0).return_value.unwrap();
+        assert_eq!(value.as_string(), Ok(String::new()));
+        let value = world.run(&param("booleanParam_b"), 0).return_value.unwrap();
+        assert!(!value.is_truthy());
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
+
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct GetDate {
+    id: Id,
+    unit: String,
+}
+
+impl GetDate {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(unit) = compiler.required_param(block, 1) else {
+            return;
+        };
+        let unit = unit.as_string().unwrap();
+
+        compiler.push(
+            GetDate {
+                id: block.id.clone().into(),
+                unit,
+            }
+            .into(),
+        );
+    }
+}
+
+/// The `unit` part of `date`, e.g. the year for `"YEAR"`.
+fn date_part<Tz: TimeZone>(date: &DateTime<Tz>, unit: &str) -> u32 {
+    match unit {
+        "YEAR" => date.year() as u32,
+        "MONTH" => date.month(),
+        "DAY" => date.day(),
+        "HOUR" => date.hour(),
+        "MINUTE" => date.minute(),
+        "SECOND" => date.second(),
+        _ => unreachable!(),
+    }
+}
+
+impl Block for GetDate {
+    fn run(
+        &self,
+        pointer: usize,
+        _memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        // Entry reports local time.
+        let result = date_part(&Local::now(), &self.unit);
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::Number(result as f32)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Utc};
+
+    use super::*;
+
+    #[test]
+    fn parts_of_a_date() {
+        // 2024-02-29T12:34:56Z
+        let date = Utc.timestamp_opt(1709210096, 0).unwrap();
+        let parts =
+            ["YEAR", "MONTH", "DAY", "HOUR", "MINUTE", "SECOND"].map(|unit| date_part(&date, unit));
+        assert_eq!(parts, [2024, 2, 29, 12, 34, 56]);
+    }
+
+    #[test]
+    fn parts_are_in_the_time_zone() {
+        let seoul = FixedOffset::east_opt(9 * 3600).unwrap();
+        // 2024-02-29T16:00:00Z
+        let date = seoul.timestamp_opt(1709222400, 0).unwrap();
+        assert_eq!(date_part(&date, "DAY"), 1);
+        assert_eq!(date_part(&date, "MONTH"), 3);
+        assert_eq!(date_part(&date, "HOUR"), 1);
+    }
+}
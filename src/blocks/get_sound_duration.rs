@@ -0,0 +1,89 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct GetSoundDuration {
+    id: Id,
+    sound: Value,
+}
+
+impl GetSoundDuration {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(sound) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        compiler.push(
+            GetSoundDuration {
+                id: block.id.clone().into(),
+                sound,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for GetSoundDuration {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let sound = self
+            .sound
+            .take_raw_value(memory)
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        let this = ctx.objects.get(*ctx.owner).unwrap();
+        let duration = this
+            .sounds
+            .iter()
+            .find(|s| s.id.0 == sound || s.name == sound)
+            .map(|s| s.duration)
+            .unwrap_or_default();
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::Number(duration)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blocks::test_world::TestWorld, object::Sound};
+
+    fn duration(world: &mut TestWorld, sound: &str) -> f32 {
+        let block = GetSoundDuration {
+            id: Id::from_str("duration"),
+            sound: Value::String(sound.to_string()),
+        };
+        let value = world.run(&block, 0).return_value.unwrap();
+        value.as_number().unwrap()
+    }
+
+    #[test]
+    fn finds_sounds_by_id_or_name() {
+        let mut world = TestWorld::new();
+        let owner = world.owner;
+        world.object_mut(owner).sounds.push(Sound {
+            id: Id::from_str("sound1"),
+            name: "박수".to_string(),
+            duration: 1.5,
+        });
+
+        assert_eq!(duration(&mut world, "sound1"), 1.5);
+        assert_eq!(duration(&mut world, "박수"), 1.5);
+        assert_eq!(duration(&mut world, "missing"), 0.0);
+    }
+}
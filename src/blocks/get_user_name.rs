@@ -0,0 +1,54 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct GetUserName {
+    id: Id,
+}
+
+impl GetUserName {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        compiler.push(
+            GetUserName {
+                id: block.id.clone().into(),
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for GetUserName {
+    fn run(
+        &self,
+        pointer: usize,
+        _memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        // Entry reports a blank name when nobody is logged in, which is always the case here.
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::String(" ".to_string())),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn nobody_is_logged_in() {
+        let block = GetUserName {
+            id: Id::from_str("user"),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        assert_eq!(value.as_string(), Ok(" ".to_string()));
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec};
+use super::{compiler::Compiler, Block, BlockReturn};
 
 #[derive(Clone)]
 pub(crate) struct GetVariable {
@@ -27,21 +27,38 @@ impl Block for GetVariable {
     }
 }
 impl GetVariable {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
-
-        let (variable_id, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(variable_id) = compiler.required_param(block, 0) else {
+            return;
+        };
 
         let variable_id = variable_id.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             GetVariable {
                 id: block.id.clone().into(),
                 variable_id,
             }
             .into(),
         );
-        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{test_world::TestWorld, Value};
+
+    #[test]
+    fn returns_the_value() {
+        let mut world = TestWorld::new();
+        world.spawn_variable("v", Value::String("엔트리".to_string()));
+        let block = GetVariable {
+            id: Id::from_str("get"),
+            variable_id: "v".to_string(),
+        };
+
+        let value = world.run(&block, 0).return_value.unwrap();
+        assert_eq!(value.as_string().unwrap(), "엔트리");
     }
 }
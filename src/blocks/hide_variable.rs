@@ -0,0 +1,62 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn};
+
+#[derive(Clone)]
+pub(crate) struct HideVariable {
+    id: Id,
+    variable_id: String,
+}
+impl Block for HideVariable {
+    fn run(&self, pointer: usize, _memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let variable_entity = ctx.ids.get(&Id::from_str(&self.variable_id)).unwrap();
+        let mut variable = ctx.variables.get_mut(*variable_entity).unwrap();
+
+        variable.visible = false;
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl HideVariable {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(variable_id) = compiler.required_param(block, 0) else {
+            return;
+        };
+        let variable_id = variable_id.as_string().unwrap();
+
+        compiler.push(
+            HideVariable {
+                id: block.id.clone().into(),
+                variable_id,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{test_world::TestWorld, Value};
+
+    #[test]
+    fn hides_the_variable() {
+        let mut world = TestWorld::new();
+        let variable = world.spawn_variable("v", Value::Number(0.0));
+        world.variable_mut(variable).visible = true;
+        let block = HideVariable {
+            id: Id::from_str("hides"),
+            variable_id: "v".to_string(),
+        };
+
+        world.run(&block, 0);
+        assert!(!world.variable(variable).visible);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Label, Value};
+
+#[derive(Clone)]
+pub(crate) struct IfElse {
+    id: Id,
+    condition: Value,
+    otherwise: Label,
+}
+impl Block for IfElse {
+    fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        let condition = self
+            .condition
+            .take_raw_value(memory)
+            .is_some_and(|value| value.is_truthy());
+        if condition {
+            BlockReturn::basic(pointer)
+        } else {
+            BlockReturn {
+                pointer: self.otherwise.pointer(),
+                is_continue: false,
+                return_value: None,
+            }
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.otherwise]
+    }
+}
+impl IfElse {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(condition) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        let otherwise = compiler.label();
+        let end = compiler.label();
+
+        compiler.push(
+            IfElse {
+                id: block.id.clone().into(),
+                condition,
+                otherwise,
+            }
+            .into(),
+        );
+        compiler.statements(&block.statements, 0);
+        compiler.push(
+            IfElseEnd {
+                id: block.id.clone().into(),
+                end,
+            }
+            .into(),
+        );
+        compiler.bind(otherwise);
+        compiler.statements(&block.statements, 1);
+        compiler.bind(end);
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct IfElseEnd {
+    id: Id,
+    end: Label,
+}
+impl Block for IfElseEnd {
+    fn run(&self, _pointer: usize, _memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        BlockReturn {
+            pointer: self.end.pointer(),
+            is_continue: false,
+            return_value: None,
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::{
+        test_world::{change_variable, literal, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn runs_one_branch_then_what_follows() {
+        let mut world = TestWorld::new();
+        let [a, b, c, d, after] =
+            ["a", "b", "c", "d", "after"].map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let script = [
+            raw_block(
+                "if_else",
+                vec![literal(true)],
+                vec![vec![change_variable("a")], vec![change_variable("b")]],
+            ),
+            raw_block(
+                "if_else",
+                vec![literal(false)],
+                vec![vec![change_variable("c")], vec![change_variable("d")]],
+            ),
+            change_variable("after"),
+        ];
+        world.spawn_code(Compiler::compile(&script));
+
+        world.tick();
+        assert_eq!(world.number(a), 1.0);
+        assert_eq!(world.number(b), 0.0);
+        assert_eq!(world.number(c), 0.0);
+        assert_eq!(world.number(d), 1.0);
+        assert_eq!(world.number(after), 1.0);
+    }
+
+    #[test]
+    fn empty_branches_fall_through() {
+        let mut world = TestWorld::new();
+        let after = world.spawn_variable("after", Value::Number(0.0));
+        let script = [
+            raw_block("if_else", vec![literal(true)], vec![]),
+            raw_block("if_else", vec![literal(false)], vec![]),
+            change_variable("after"),
+        ];
+        world.spawn_code(Compiler::compile(&script));
+
+        world.tick();
+        assert_eq!(world.number(after), 1.0);
+        assert_eq!(world.runners(), 0);
+    }
+}
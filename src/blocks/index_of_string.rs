@@ -0,0 +1,80 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct IndexOfString {
+    id: Id,
+    value: Value,
+    target: Value,
+}
+
+impl IndexOfString {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(target) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        compiler.push(
+            IndexOfString {
+                id: block.id.clone().into(),
+                value,
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+fn index_of(value: &str, target: &str) -> f32 {
+    match value.find(target) {
+        Some(index) => value[..index].chars().count() as f32 + 1.0,
+        None => 0.0,
+    }
+}
+
+impl Block for IndexOfString {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self.value.take_raw_value(memory).unwrap();
+        let target = self.target.take_raw_value(memory).unwrap();
+
+        let value = value.as_string().unwrap();
+        let target = target.as_string().unwrap();
+
+        let result = Value::Number(index_of(&value, &target));
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::index_of;
+
+    #[test]
+    fn index_of_is_one_based() {
+        assert_eq!(index_of("엔트리", "트"), 2.0);
+        assert_eq!(index_of("abcabc", "c"), 3.0);
+    }
+
+    #[test]
+    fn index_of_missing_is_zero() {
+        assert_eq!(index_of("abc", "d"), 0.0);
+    }
+}
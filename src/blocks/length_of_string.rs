@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct LengthOfString {
@@ -33,19 +33,17 @@ impl Block for LengthOfString {
     }
 }
 impl LengthOfString {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (value, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        blocks.push(
+        compiler.push(
             LengthOfString {
                 id: block.id.clone().into(),
                 value,
             }
             .into(),
         );
-        blocks
     }
 }
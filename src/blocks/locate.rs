@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockVec};
+use super::{compiler::Compiler, Block};
 
 #[derive(Clone)]
 pub(crate) struct Locate {
@@ -9,21 +9,19 @@ pub(crate) struct Locate {
 }
 
 impl Locate {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
-
-        let (target, _) = parse_param(&block.params[0]).unwrap();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(target) = compiler.required_param(block, 0) else {
+            return;
+        };
         let target = target.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             Locate {
                 id: block.id.clone().into(),
                 target,
             }
             .into(),
         );
-
-        blocks
     }
 }
 
@@ -57,3 +55,34 @@ impl Block for Locate {
         &self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn locate(world: &mut TestWorld, target: &str) -> Vec2 {
+        let block = Locate {
+            id: Id::from_str("locate"),
+            target: target.to_string(),
+        };
+        world.run(&block, 0);
+        world.this().translation.truncate()
+    }
+
+    #[test]
+    fn moves_to_the_mouse() {
+        let mut world = TestWorld::new();
+        world.set_mouse(Vec2::new(12.0, 34.0));
+        assert_eq!(locate(&mut world, "mouse"), Vec2::new(12.0, 34.0));
+    }
+
+    #[test]
+    fn moves_to_another_object() {
+        let mut world = TestWorld::new();
+        world.spawn_object("other", Vec2::new(-50.0, 20.0));
+        assert_eq!(locate(&mut world, "other"), Vec2::new(-50.0, 20.0));
+    }
+}
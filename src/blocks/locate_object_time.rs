@@ -0,0 +1,121 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, timed::animate, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct LocateObjectTime {
+    id: Id,
+    time: Value,
+    target: String,
+}
+impl Block for LocateObjectTime {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let time = memory
+            .cache(&self.id, "time", &self.time)
+            .as_number()
+            .unwrap();
+
+        // The target is where the object or the mouse pointer was when the glide started.
+        let target = match &self.target[..] {
+            "mouse" => ctx.mouse.pos,
+            target => {
+                let entity = ctx.ids.get(&Id::from_str(target)).unwrap();
+                ctx.objects.get(*entity).unwrap().translation.truncate()
+            }
+        };
+        let this = ctx.objects.get(*ctx.owner).unwrap();
+        let dx = memory.cache(
+            &self.id,
+            "dx",
+            &Value::Number(target.x - this.translation.x),
+        );
+        let dx = dx.as_number().unwrap();
+        let dy = memory.cache(
+            &self.id,
+            "dy",
+            &Value::Number(target.y - this.translation.y),
+        );
+        let dy = dy.as_number().unwrap();
+
+        animate(
+            &self.id,
+            pointer,
+            memory,
+            ctx,
+            time,
+            &["time", "dx", "dy"],
+            |share, ctx| {
+                let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+                this.translation.x += share * dx;
+                this.translation.y += share * dy;
+            },
+        )
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl LocateObjectTime {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(time) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        let Some(target) = compiler.required_param(block, 1) else {
+            return;
+        };
+        let target = target.as_string().unwrap();
+
+        compiler.push(
+            LocateObjectTime {
+                id: block.id.clone().into(),
+                time,
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn glide(target: &str) -> LocateObjectTime {
+        LocateObjectTime {
+            id: Id::from_str("glide"),
+            time: Value::Number(2.0),
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn glides_to_where_the_mouse_was() {
+        let mut world = TestWorld::new();
+        world.step = 0.5;
+        world.set_mouse(Vec2::new(40.0, 80.0));
+
+        assert!(world.run(&glide("mouse"), 0).is_continue);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(10.0, 20.0));
+        world.set_mouse(Vec2::new(-100.0, -100.0));
+        assert_eq!(world.run_timed(&glide("mouse")), 3);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(40.0, 80.0));
+    }
+
+    #[test]
+    fn glides_to_another_object() {
+        let mut world = TestWorld::new();
+        world.step = 0.5;
+        world.spawn_object("other", Vec2::new(-20.0, 60.0));
+
+        assert_eq!(world.run_timed(&glide("other")), 4);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(-20.0, 60.0));
+    }
+}
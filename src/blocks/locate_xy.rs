@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockVec, Value};
+use super::{compiler::Compiler, Block, Value};
 
 #[derive(Clone)]
 pub(crate) struct LocateXY {
@@ -10,16 +10,16 @@ pub(crate) struct LocateXY {
 }
 
 impl LocateXY {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(x) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (x, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(y) = compiler.required_param(block, 1) else {
+            return;
+        };
 
-        let (y, mut param_blocks) = parse_param(&block.params[1]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        blocks.push(
+        compiler.push(
             LocateXY {
                 id: block.id.clone().into(),
                 x,
@@ -27,8 +27,6 @@ impl LocateXY {
             }
             .into(),
         );
-
-        blocks
     }
 }
 
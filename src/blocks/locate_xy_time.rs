@@ -0,0 +1,98 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, timed::animate, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct LocateXYTime {
+    id: Id,
+    time: Value,
+    x: Value,
+    y: Value,
+}
+impl Block for LocateXYTime {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let time = memory
+            .cache(&self.id, "time", &self.time)
+            .as_number()
+            .unwrap();
+        let x = memory.cache(&self.id, "x", &self.x).as_number().unwrap();
+        let y = memory.cache(&self.id, "y", &self.y).as_number().unwrap();
+
+        let this = ctx.objects.get(*ctx.owner).unwrap();
+        let dx = memory.cache(&self.id, "dx", &Value::Number(x - this.translation.x));
+        let dx = dx.as_number().unwrap();
+        let dy = memory.cache(&self.id, "dy", &Value::Number(y - this.translation.y));
+        let dy = dy.as_number().unwrap();
+
+        animate(
+            &self.id,
+            pointer,
+            memory,
+            ctx,
+            time,
+            &["time", "x", "y", "dx", "dy"],
+            |share, ctx| {
+                let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+                this.translation.x += share * dx;
+                this.translation.y += share * dy;
+            },
+        )
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl LocateXYTime {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(time) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        let Some(x) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(y) = compiler.required_param(block, 2) else {
+            return;
+        };
+
+        compiler.push(
+            LocateXYTime {
+                id: block.id.clone().into(),
+                time,
+                x,
+                y,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn glides_to_the_point() {
+        let mut world = TestWorld::new();
+        world.step = 0.5;
+        let block = LocateXYTime {
+            id: Id::from_str("glide"),
+            time: Value::Number(1.0),
+            x: Value::Number(-40.0),
+            y: Value::Number(20.0),
+        };
+
+        assert!(world.run(&block, 0).is_continue);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(-20.0, 10.0));
+        assert_eq!(world.run_timed(&block), 1);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(-40.0, 20.0));
+    }
+}
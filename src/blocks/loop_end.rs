@@ -0,0 +1,31 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{Block, BlockReturn, Label};
+
+/// End of every loop's statements: jumps back to the loop block and yields, so a loop runs at
+/// most one iteration per tick like in Entry, even when its statements never wait.
+#[derive(Clone)]
+pub(crate) struct LoopEnd {
+    pub(crate) id: Id,
+    pub(crate) start: Label,
+}
+impl Block for LoopEnd {
+    fn run(&self, _pointer: usize, _memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        BlockReturn {
+            pointer: self.start.pointer(),
+            is_continue: true,
+            return_value: None,
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.start]
+    }
+}
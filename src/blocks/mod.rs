@@ -1,13 +1,36 @@
 mod _if;
+mod boolean_and_or;
 mod boolean_basic_operator;
+mod boolean_not;
 mod calc_basic;
 mod calc_operation;
+mod calc_rand;
+mod call_function;
+mod change_string_case;
 mod change_variable;
+mod char_at;
+mod combine_something;
+mod compiler;
+mod continue_repeat;
+mod coordinate_mouse;
 mod coordinate_object;
+mod count_match_string;
+mod direction_relative_duration;
+mod distance_something;
+mod function_param;
+mod get_date;
+mod get_sound_duration;
+mod get_user_name;
 mod get_variable;
+mod hide_variable;
+mod if_else;
+mod index_of_string;
 mod length_of_string;
 mod locate;
+mod locate_object_time;
 mod locate_xy;
+mod locate_xy_time;
+mod loop_end;
 mod move_direction;
 mod move_x;
 mod move_xy_time;
@@ -15,13 +38,30 @@ mod move_y;
 mod quotient_and_mod;
 mod repeat_basic;
 mod repeat_inf;
+mod repeat_while_true;
+mod replace_string;
+mod restart_project;
+mod reverse_of_string;
+mod rotate_by_time;
+mod see_angle_object;
 mod set_variable;
+mod show_variable;
+mod stop_object;
+mod stop_repeat;
+mod substring;
+#[cfg(test)]
+pub(crate) mod test_world;
+mod text_append;
+mod text_change_bg_color;
+mod text_change_font_color;
+mod text_prepend;
+mod text_read;
+mod text_write;
+mod timed;
 mod wait_second;
+mod wait_until_true;
 
-use std::str::FromStr;
-
-use bevy::prelude::*;
-use dotent::project::script::Param;
+use bevy::prelude::warn;
 use enum_dispatch::enum_dispatch;
 use strum::{Display, EnumDiscriminants, EnumString};
 
@@ -30,26 +70,67 @@ use crate::{
     common::Id,
 };
 
+pub(crate) use self::compiler::{Compiler, Label};
+
 use self::{
     _if::If,
+    boolean_and_or::BooleanAndOr,
     boolean_basic_operator::BooleanBasicOperator,
+    boolean_not::BooleanNot,
     calc_basic::CalcBasic,
     calc_operation::CalcOperation,
+    calc_rand::CalcRand,
+    call_function::CallFunction,
+    change_string_case::ChangeStringCase,
     change_variable::ChangeVariable,
+    char_at::CharAt,
+    combine_something::CombineSomething,
+    continue_repeat::ContinueRepeat,
+    coordinate_mouse::CoordinateMouse,
     coordinate_object::CoordinateObject,
+    count_match_string::CountMatchString,
+    direction_relative_duration::DirectionRelativeDuration,
+    distance_something::DistanceSomething,
+    function_param::FunctionParam,
+    get_date::GetDate,
+    get_sound_duration::GetSoundDuration,
+    get_user_name::GetUserName,
     get_variable::GetVariable,
+    hide_variable::HideVariable,
+    if_else::{IfElse, IfElseEnd},
+    index_of_string::IndexOfString,
     length_of_string::LengthOfString,
     locate::Locate,
+    locate_object_time::LocateObjectTime,
     locate_xy::LocateXY,
+    locate_xy_time::LocateXYTime,
+    loop_end::LoopEnd,
     move_direction::MoveDirection,
     move_x::MoveX,
     move_xy_time::MoveXYTime,
     move_y::MoveY,
     quotient_and_mod::QuotientAndMod,
-    repeat_basic::{RepeatBasic, RepeatBasicEnd},
+    repeat_basic::RepeatBasic,
     repeat_inf::RepeatInf,
+    repeat_while_true::RepeatWhileTrue,
+    replace_string::ReplaceString,
+    restart_project::RestartProject,
+    reverse_of_string::ReverseOfString,
+    rotate_by_time::RotateByTime,
+    see_angle_object::SeeAngleObject,
     set_variable::SetVariable,
+    show_variable::ShowVariable,
+    stop_object::StopObject,
+    stop_repeat::StopRepeat,
+    substring::Substring,
+    text_append::TextAppend,
+    text_change_bg_color::TextChangeBgColor,
+    text_change_font_color::TextChangeFontColor,
+    text_prepend::TextPrepend,
+    text_read::TextRead,
+    text_write::TextWrite,
     wait_second::WaitSecond,
+    wait_until_true::WaitUntilTrue,
 };
 
 #[enum_dispatch]
@@ -62,7 +143,8 @@ pub(crate) enum BlockEnum {
     MoveDirection,
     WaitSecond,
     RepeatBasic,
-    RepeatBasicEnd,
+    #[strum_discriminants(strum(disabled))]
+    LoopEnd,
     LengthOfString,
     SetVariable,
     GetVariable,
@@ -80,30 +162,116 @@ pub(crate) enum BlockEnum {
     QuotientAndMod,
     CoordinateObject,
     Locate,
+    IfElse,
+    #[strum_discriminants(strum(disabled))]
+    IfElseEnd,
+    RepeatWhileTrue,
+    WaitUntilTrue,
+    StopRepeat,
+    ContinueRepeat,
+    StopObject,
+    RestartProject,
+    #[strum_discriminants(strum(disabled))]
+    CallFunction,
+    #[strum_discriminants(strum(disabled))]
+    FunctionParam,
+    CalcRand,
+    ChangeStringCase,
+    CharAt,
+    CombineSomething,
+    CoordinateMouse,
+    CountMatchString,
+    DistanceSomething,
+    GetDate,
+    GetSoundDuration,
+    GetUserName,
+    IndexOfString,
+    ReplaceString,
+    ReverseOfString,
+    Substring,
+    BooleanAndOr,
+    BooleanNot,
+    ShowVariable,
+    HideVariable,
+    TextAppend,
+    TextChangeBgColor,
+    TextChangeFontColor,
+    TextPrepend,
+    TextRead,
+    TextWrite,
+    LocateXYTime,
+    LocateObjectTime,
+    RotateByTime,
+    DirectionRelativeDuration,
+    SeeAngleObject,
 }
 impl BlockType {
-    pub(crate) fn build(&self, block: &dotent::project::script::Block) -> BlockVec {
+    pub(crate) fn build(&self, block: &dotent::project::script::Block, compiler: &mut Compiler) {
         match self {
-            BlockType::MoveDirection => MoveDirection::build(block),
-            BlockType::WaitSecond => WaitSecond::build(block),
-            BlockType::RepeatBasic => RepeatBasic::build(block),
-            BlockType::RepeatBasicEnd => unreachable!(),
-            BlockType::RepeatInf => RepeatInf::build(block),
-            BlockType::LengthOfString => LengthOfString::build(block),
-            BlockType::SetVariable => SetVariable::build(block),
-            BlockType::GetVariable => GetVariable::build(block),
-            BlockType::ChangeVariable => ChangeVariable::build(block),
-            BlockType::If => If::build(block),
-            BlockType::BooleanBasicOperator => BooleanBasicOperator::build(block),
-            BlockType::MoveX => MoveX::build(block),
-            BlockType::MoveY => MoveY::build(block),
-            BlockType::CalcBasic => CalcBasic::build(block),
-            BlockType::MoveXYTime => MoveXYTime::build(block),
-            BlockType::LocateXY => LocateXY::build(block),
-            BlockType::CalcOperation => CalcOperation::build(block),
-            BlockType::QuotientAndMod => QuotientAndMod::build(block),
-            BlockType::CoordinateObject => CoordinateObject::build(block),
-            BlockType::Locate => Locate::build(block),
+            BlockType::MoveDirection => MoveDirection::build(block, compiler),
+            BlockType::WaitSecond => WaitSecond::build(block, compiler),
+            BlockType::RepeatBasic => RepeatBasic::build(block, compiler),
+            BlockType::RepeatInf => RepeatInf::build(block, compiler),
+            BlockType::LengthOfString => LengthOfString::build(block, compiler),
+            BlockType::SetVariable => SetVariable::build(block, compiler),
+            BlockType::GetVariable => GetVariable::build(block, compiler),
+            BlockType::ChangeVariable => ChangeVariable::build(block, compiler),
+            BlockType::If => If::build(block, compiler),
+            BlockType::BooleanBasicOperator => BooleanBasicOperator::build(block, compiler),
+            BlockType::MoveX => MoveX::build(block, compiler),
+            BlockType::MoveY => MoveY::build(block, compiler),
+            BlockType::CalcBasic => CalcBasic::build(block, compiler),
+            BlockType::MoveXYTime => MoveXYTime::build(block, compiler),
+            BlockType::LocateXY => LocateXY::build(block, compiler),
+            BlockType::CalcOperation => CalcOperation::build(block, compiler),
+            BlockType::QuotientAndMod => QuotientAndMod::build(block, compiler),
+            BlockType::CoordinateObject => CoordinateObject::build(block, compiler),
+            BlockType::Locate => Locate::build(block, compiler),
+            BlockType::IfElse => IfElse::build(block, compiler),
+            BlockType::RepeatWhileTrue => RepeatWhileTrue::build(block, compiler),
+            BlockType::WaitUntilTrue => WaitUntilTrue::build(block, compiler),
+            BlockType::StopRepeat => StopRepeat::build(block, compiler),
+            BlockType::ContinueRepeat => ContinueRepeat::build(block, compiler),
+            BlockType::StopObject => StopObject::build(block, compiler),
+            BlockType::RestartProject => RestartProject::build(block, compiler),
+            BlockType::CalcRand => CalcRand::build(block, compiler),
+            BlockType::ChangeStringCase => ChangeStringCase::build(block, compiler),
+            BlockType::CharAt => CharAt::build(block, compiler),
+            BlockType::CombineSomething => CombineSomething::build(block, compiler),
+            BlockType::CoordinateMouse => CoordinateMouse::build(block, compiler),
+            BlockType::CountMatchString => CountMatchString::build(block, compiler),
+            BlockType::DistanceSomething => DistanceSomething::build(block, compiler),
+            BlockType::GetDate => GetDate::build(block, compiler),
+            BlockType::GetSoundDuration => GetSoundDuration::build(block, compiler),
+            BlockType::GetUserName => GetUserName::build(block, compiler),
+            BlockType::IndexOfString => IndexOfString::build(block, compiler),
+            BlockType::ReplaceString => ReplaceString::build(block, compiler),
+            BlockType::ReverseOfString => ReverseOfString::build(block, compiler),
+            BlockType::Substring => Substring::build(block, compiler),
+            BlockType::BooleanAndOr => BooleanAndOr::build(block, compiler),
+            BlockType::BooleanNot => BooleanNot::build(block, compiler),
+            BlockType::ShowVariable => ShowVariable::build(block, compiler),
+            BlockType::HideVariable => HideVariable::build(block, compiler),
+            BlockType::TextAppend => TextAppend::build(block, compiler),
+            BlockType::TextChangeBgColor => TextChangeBgColor::build(block, compiler),
+            BlockType::TextChangeFontColor => TextChangeFontColor::build(block, compiler),
+            BlockType::TextPrepend => TextPrepend::build(block, compiler),
+            BlockType::TextRead => TextRead::build(block, compiler),
+            BlockType::TextWrite => TextWrite::build(block, compiler),
+            BlockType::LocateXYTime => LocateXYTime::build(block, compiler),
+            BlockType::LocateObjectTime => LocateObjectTime::build(block, compiler),
+            BlockType::RotateByTime => RotateByTime::build(block, compiler),
+            BlockType::DirectionRelativeDuration => {
+                DirectionRelativeDuration::build(block, compiler)
+            }
+            BlockType::SeeAngleObject => SeeAngleObject::build(block, compiler),
+            // Emitted by the compiler itself, never parsed from a block type.
+            BlockType::LoopEnd
+            | BlockType::IfElseEnd
+            | BlockType::CallFunction
+            | BlockType::FunctionParam => {
+                warn!("unsupported block: {}", block.block_type)
+            }
         }
     }
 }
@@ -115,6 +283,9 @@ pub(crate) enum LiteralBlockType {
     #[strum(serialize = "text")]
     Text,
     True,
+    False,
+    #[strum(serialize = "get_sounds")]
+    GetSounds,
 }
 
 #[derive(Clone, Debug)]
@@ -152,10 +323,13 @@ impl Value {
             _ => Err("cannot convert as mutable number"),
         }
     }
-    pub(crate) fn as_bool(&self) -> Option<bool> {
+    /// Entry's condition semantics: `false`, `0`, `""` and `"false"` are false.
+    pub(crate) fn is_truthy(&self) -> bool {
         match self {
-            Self::Bool(val) => Some(*val),
-            _ => None,
+            Self::Bool(val) => *val,
+            Self::Number(val) => *val != 0.0 && !val.is_nan(),
+            Self::String(val) => !val.is_empty() && val != "false",
+            Self::Memory(_) => false,
         }
     }
     #[allow(dead_code)]
@@ -183,51 +357,10 @@ impl From<dotent::project::variable::Value> for Value {
     }
 }
 
-pub(crate) fn parse_block(raw_block: &dotent::project::script::Block) -> BlockVec {
-    if let Ok(block_type) = BlockType::from_str(&raw_block.block_type) {
-        block_type.build(raw_block)
-    } else {
-        warn!("unsupported block: {}", raw_block.block_type);
-        Vec::new()
-    }
-}
-fn parse_param(param: &Param) -> Option<(Value, BlockVec)> {
-    let mut blocks = Vec::new();
-    let val = match param {
-        Param::Block(block) => {
-            if let Ok(block_type) = BlockType::from_str(&block.block_type) {
-                let mut param_blocks = block_type.build(block);
-                let last_id = param_blocks.last().unwrap().get_id().clone();
-                blocks.append(&mut param_blocks);
-                Value::Memory((last_id, "return_value".to_string()))
-            } else if LiteralBlockType::from_str(&block.block_type).is_ok() {
-                return parse_param(&block.params[0]);
-            } else {
-                warn!("unsupported block: {}", block.block_type);
-                return None;
-            }
-        }
-        Param::Number(val) => Value::Number(*val),
-        Param::String(val) => Value::String(val.to_string()),
-        Param::Bool(val) => Value::Bool(*val),
-        Param::Null => return None,
-    };
-    Some((val, blocks))
-}
-fn parse_statements(script: &dotent::project::script::Script) -> Vec<BlockVec> {
-    let mut codes = Vec::new();
-    for code in script.0.iter() {
-        let mut blocks = Vec::new();
-        for block in code {
-            blocks.append(&mut parse_block(block));
-        }
-        codes.push(blocks);
-    }
-    codes
-}
-
 pub(crate) struct BlockReturn {
     pub(crate) pointer: usize,
+    /// Yields the runner until the next tick, like Entry's `STATIC.BREAK`.
+    /// Waiting blocks yield while they wait and loops yield once per iteration at their end block.
     pub(crate) is_continue: bool,
     pub(crate) return_value: Option<Value>,
 }
@@ -247,4 +380,7 @@ pub(crate) type BlockVec = Vec<BlockEnum>;
 pub(crate) trait Block {
     fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn;
     fn get_id(&self) -> &Id;
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        Vec::new()
+    }
 }
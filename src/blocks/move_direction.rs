@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct MoveDirection {
@@ -30,17 +30,16 @@ impl Block for MoveDirection {
     }
 }
 impl MoveDirection {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
-        let (amount, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-        blocks.push(
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(amount) = compiler.required_param(block, 0) else {
+            return;
+        };
+        compiler.push(
             MoveDirection {
                 id: block.id.clone().into(),
                 amount,
             }
             .into(),
         );
-        blocks
     }
 }
@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockVec, Value};
+use super::{compiler::Compiler, Block, Value};
 
 #[derive(Clone)]
 pub(crate) struct MoveX {
@@ -9,21 +9,18 @@ pub(crate) struct MoveX {
 }
 
 impl MoveX {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(amount) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (amount, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        blocks.push(
+        compiler.push(
             MoveX {
                 id: block.id.clone().into(),
                 amount,
             }
             .into(),
         );
-
-        blocks
     }
 }
 
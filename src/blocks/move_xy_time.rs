@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, timed::animate, Block, Value};
 
 #[derive(Clone)]
 pub(crate) struct MoveXYTime {
@@ -11,19 +11,20 @@ pub(crate) struct MoveXYTime {
 }
 
 impl MoveXYTime {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(time) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (time, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(x) = compiler.required_param(block, 1) else {
+            return;
+        };
 
-        let (x, mut param_blocks) = parse_param(&block.params[1]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(y) = compiler.required_param(block, 2) else {
+            return;
+        };
 
-        let (y, mut param_blocks) = parse_param(&block.params[2]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        blocks.push(
+        compiler.push(
             MoveXYTime {
                 id: block.id.clone().into(),
                 time,
@@ -32,8 +33,6 @@ impl MoveXYTime {
             }
             .into(),
         );
-
-        blocks
     }
 }
 
@@ -51,38 +50,48 @@ impl Block for MoveXYTime {
         let x = memory.cache(&self.id, "x", &self.x).as_number().unwrap();
         let y = memory.cache(&self.id, "y", &self.y).as_number().unwrap();
 
-        let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
-
-        let delta = memory
-            .entry(&self.id, "delta")
-            .or_insert(Value::Number(0.0))
-            .as_number_mut()
-            .unwrap();
-
-        let this_delta = (ctx.time.delta_seconds() / time).min(1.0);
-
-        let new_delta = *delta + this_delta;
-
-        if new_delta < 1.0 {
-            this.translation.x += this_delta * x;
-            this.translation.y += this_delta * y;
-            *delta = new_delta;
-            BlockReturn {
-                pointer,
-                is_continue: true,
-                return_value: None,
-            }
-        } else {
-            let this_delta = 1.0 - *delta;
-            this.translation.x += this_delta * x;
-            this.translation.y += this_delta * y;
-
-            memory.remove_many(&self.id, &["delta", "time", "x", "y"]);
-            BlockReturn::basic(pointer)
-        }
+        animate(
+            &self.id,
+            pointer,
+            memory,
+            ctx,
+            time,
+            &["time", "x", "y"],
+            |share, ctx| {
+                let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+                this.translation.x += share * x;
+                this.translation.y += share * y;
+            },
+        )
     }
 
     fn get_id(&self) -> &Id {
         &self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn moves_across_ticks() {
+        let mut world = TestWorld::new();
+        world.step = 0.5;
+        let block = MoveXYTime {
+            id: Id::from_str("move"),
+            time: Value::Number(1.5),
+            x: Value::Number(30.0),
+            y: Value::Number(-15.0),
+        };
+
+        assert!(world.run(&block, 0).is_continue);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(10.0, -5.0));
+
+        assert_eq!(world.run_timed(&block), 2);
+        assert_eq!(world.this().translation.truncate(), Vec2::new(30.0, -15.0));
+    }
+}
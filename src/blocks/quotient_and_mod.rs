@@ -1,6 +1,6 @@
 use crate::common::Id;
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct QuotientAndMod {
@@ -11,19 +11,21 @@ pub(crate) struct QuotientAndMod {
 }
 
 impl QuotientAndMod {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks = Vec::new();
-
-        let (left, mut param_blocks) = parse_param(&block.params[1]).unwrap();
-        blocks.append(&mut param_blocks);
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(left) = compiler.required_param(block, 1) else {
+            return;
+        };
 
-        let (right, mut param_blocks) = parse_param(&block.params[3]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(right) = compiler.required_param(block, 3) else {
+            return;
+        };
 
-        let (operator, _) = parse_param(&block.params[5]).unwrap();
+        let Some(operator) = compiler.required_param(block, 5) else {
+            return;
+        };
         let operator = operator.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             QuotientAndMod {
                 id: block.id.clone().into(),
                 left,
@@ -32,8 +34,6 @@ impl QuotientAndMod {
             }
             .into(),
         );
-
-        blocks
     }
 }
 
@@ -74,3 +74,41 @@ impl Block for QuotientAndMod {
         &self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn calc(left: f32, operator: &str, right: f32) -> f32 {
+        let block = QuotientAndMod {
+            id: Id::from_str("quotient_and_mod"),
+            left: Value::Number(left),
+            right: Value::Number(right),
+            operator: operator.to_string(),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.as_number().unwrap()
+    }
+
+    #[test]
+    fn quotient_rounds_down() {
+        assert_eq!(calc(7.0, "QUOTIENT", 2.0), 3.0);
+        assert_eq!(calc(-7.0, "QUOTIENT", 2.0), -4.0);
+        assert_eq!(calc(7.0, "QUOTIENT", -2.0), -4.0);
+    }
+
+    #[test]
+    fn mod_keeps_the_sign_of_the_left_side() {
+        // Entry uses JavaScript's `%`.
+        assert_eq!(calc(7.0, "MOD", 2.0), 1.0);
+        assert_eq!(calc(-7.0, "MOD", 2.0), -1.0);
+        assert_eq!(calc(7.0, "MOD", -2.0), 1.0);
+    }
+
+    #[test]
+    fn dividing_by_zero() {
+        assert_eq!(calc(7.0, "QUOTIENT", 0.0), f32::INFINITY);
+        assert!(calc(7.0, "MOD", 0.0).is_nan());
+    }
+}
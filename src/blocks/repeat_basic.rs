@@ -3,13 +3,20 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, parse_statements, Block, BlockReturn, BlockVec, Value};
+use super::{
+    compiler::{Compiler, LoopFrame},
+    loop_end::LoopEnd,
+    Block, BlockReturn, Label, Value,
+};
 
 #[derive(Clone)]
 pub(crate) struct RepeatBasic {
     id: Id,
     iter_num: Value,
-    statements_length: usize,
+    end: Label,
+}
+impl RepeatBasic {
+    pub(crate) const MEMORY: &'static [&'static str] = &["count", "iter_num"];
 }
 impl Block for RepeatBasic {
     fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
@@ -30,9 +37,9 @@ impl Block for RepeatBasic {
                 return_value: None,
             }
         } else {
-            memory.remove_many(&self.id, &["count", "iter_num"]);
+            memory.remove_many(&self.id, Self::MEMORY);
             BlockReturn {
-                pointer: pointer + self.statements_length + 2,
+                pointer: self.end.pointer(),
                 is_continue: false,
                 return_value: None,
             }
@@ -42,52 +49,94 @@ impl Block for RepeatBasic {
     fn get_id(&self) -> &Id {
         &self.id
     }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.end]
+    }
 }
 impl RepeatBasic {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(iter_num) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (iter_num, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
+        let start = compiler.label();
+        let next = compiler.label();
+        let end = compiler.label();
 
-        let mut statements = parse_statements(&block.statements);
-        let statement_len = statements[0].len();
-
-        blocks.push(
+        compiler.bind(start);
+        compiler.push(
             RepeatBasic {
                 id: block.id.clone().into(),
                 iter_num,
-                statements_length: statement_len,
+                end,
             }
             .into(),
         );
-        blocks.append(&mut statements[0]);
-        blocks.push(
-            RepeatBasicEnd {
+        compiler.begin_loop(LoopFrame {
+            id: block.id.clone().into(),
+            memory: Self::MEMORY,
+            next,
+            end,
+        });
+        compiler.statements(&block.statements, 0);
+        compiler.end_loop();
+        compiler.bind(next);
+        compiler.push(
+            LoopEnd {
                 id: block.id.clone().into(),
-                statements_length: statement_len,
+                start,
             }
             .into(),
         );
-        blocks
+        compiler.bind(end);
     }
 }
 
-#[derive(Clone)]
-pub(crate) struct RepeatBasicEnd {
-    pub(crate) id: Id,
-    pub(crate) statements_length: usize,
-}
-impl Block for RepeatBasicEnd {
-    fn run(&self, pointer: usize, _memory: &mut Memory, _res: &mut Context) -> BlockReturn {
-        BlockReturn {
-            pointer: pointer - self.statements_length - 1,
-            is_continue: true,
-            return_value: None,
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use crate::blocks::{
+        test_world::{change_variable, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn nested_loops_run_every_iteration() {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let inner = raw_block(
+            "repeat_basic",
+            vec![Param::Number(3.0)],
+            vec![vec![change_variable("n")]],
+        );
+        let outer = raw_block("repeat_basic", vec![Param::Number(2.0)], vec![vec![inner]]);
+        world.spawn_code(Compiler::compile(&[outer]));
+
+        for _ in 0..20 {
+            world.tick();
         }
+        assert_eq!(world.number(n), 6.0);
+        assert_eq!(world.runners(), 0);
     }
 
-    fn get_id(&self) -> &Id {
-        &self.id
+    #[test]
+    fn counts_again_when_run_again() {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let repeat = raw_block(
+            "repeat_basic",
+            vec![Param::Number(2.0)],
+            vec![vec![change_variable("n")]],
+        );
+        let blocks = Compiler::compile(&[repeat]);
+        world.spawn_code(blocks.clone());
+        world.spawn_code(blocks);
+
+        for _ in 0..10 {
+            world.tick();
+        }
+        assert_eq!(world.number(n), 4.0);
     }
 }
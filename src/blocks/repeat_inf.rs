@@ -3,7 +3,11 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_statements, repeat_basic::RepeatBasicEnd, Block, BlockReturn, BlockVec};
+use super::{
+    compiler::{Compiler, LoopFrame},
+    loop_end::LoopEnd,
+    Block, BlockReturn,
+};
 
 #[derive(Clone)]
 pub(crate) struct RepeatInf {
@@ -19,26 +23,56 @@ impl Block for RepeatInf {
     }
 }
 impl RepeatInf {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
-
-        let mut statements = parse_statements(&block.statements);
-        let statement_len = statements[0].len();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let start = compiler.label();
+        let next = compiler.label();
+        let end = compiler.label();
 
-        blocks.push(
+        compiler.bind(start);
+        compiler.push(
             RepeatInf {
                 id: block.id.clone().into(),
             }
             .into(),
         );
-        blocks.append(&mut statements[0]);
-        blocks.push(
-            RepeatBasicEnd {
+        compiler.begin_loop(LoopFrame {
+            id: block.id.clone().into(),
+            memory: &[],
+            next,
+            end,
+        });
+        compiler.statements(&block.statements, 0);
+        compiler.end_loop();
+        compiler.bind(next);
+        compiler.push(
+            LoopEnd {
                 id: block.id.clone().into(),
-                statements_length: statement_len,
+                start,
             }
             .into(),
         );
-        blocks
+        compiler.bind(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::{
+        test_world::{change_variable, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn runs_one_iteration_per_tick_forever() {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let repeat = raw_block("repeat_inf", vec![], vec![vec![change_variable("n")]]);
+        world.spawn_code(Compiler::compile(&[repeat]));
+
+        for _ in 0..50 {
+            world.tick();
+        }
+        assert_eq!(world.number(n), 50.0);
+        assert_eq!(world.runners(), 1);
     }
 }
@@ -0,0 +1,131 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{
+    compiler::{Compiler, LoopFrame},
+    loop_end::LoopEnd,
+    Block, BlockReturn, Label, Value,
+};
+
+#[derive(Clone)]
+pub(crate) struct RepeatWhileTrue {
+    id: Id,
+    condition: Value,
+    option: String,
+    end: Label,
+}
+impl Block for RepeatWhileTrue {
+    fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        let condition = self
+            .condition
+            .take_raw_value(memory)
+            .is_some_and(|value| value.is_truthy());
+
+        let repeat = match &self.option[..] {
+            "until" => !condition,
+            "while" => condition,
+            _ => unreachable!(),
+        };
+
+        if repeat {
+            BlockReturn::basic(pointer)
+        } else {
+            BlockReturn {
+                pointer: self.end.pointer(),
+                is_continue: false,
+                return_value: None,
+            }
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.end]
+    }
+}
+impl RepeatWhileTrue {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let start = compiler.label();
+        let next = compiler.label();
+        let end = compiler.label();
+
+        compiler.bind(start);
+        let Some(condition) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        let Some(option) = compiler.required_param(block, 1) else {
+            return;
+        };
+        let option = option.as_string().unwrap();
+
+        compiler.push(
+            RepeatWhileTrue {
+                id: block.id.clone().into(),
+                condition,
+                option,
+                end,
+            }
+            .into(),
+        );
+        compiler.begin_loop(LoopFrame {
+            id: block.id.clone().into(),
+            memory: &[],
+            next,
+            end,
+        });
+        compiler.statements(&block.statements, 0);
+        compiler.end_loop();
+        compiler.bind(next);
+        compiler.push(
+            LoopEnd {
+                id: block.id.clone().into(),
+                start,
+            }
+            .into(),
+        );
+        compiler.bind(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use crate::blocks::{
+        test_world::{change_variable, compare_variable, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    fn repeat(option: &str, op: &str, value: f32) -> f32 {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let repeat = raw_block(
+            "repeat_while_true",
+            vec![
+                compare_variable("n", op, value),
+                Param::String(option.to_string()),
+            ],
+            vec![vec![change_variable("n")]],
+        );
+        world.spawn_code(Compiler::compile(&[repeat]));
+
+        for _ in 0..20 {
+            world.tick();
+        }
+        assert_eq!(world.runners(), 0);
+        world.number(n)
+    }
+
+    #[test]
+    fn checks_the_condition_before_each_iteration() {
+        assert_eq!(repeat("while", "LESS", 3.0), 3.0);
+        assert_eq!(repeat("until", "EQUAL", 5.0), 5.0);
+        assert_eq!(repeat("while", "GREATER", 0.0), 0.0);
+    }
+}
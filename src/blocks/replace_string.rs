@@ -0,0 +1,89 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct ReplaceString {
+    id: Id,
+    value: Value,
+    from: Value,
+    to: Value,
+}
+
+impl ReplaceString {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(from) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        let Some(to) = compiler.required_param(block, 5) else {
+            return;
+        };
+
+        compiler.push(
+            ReplaceString {
+                id: block.id.clone().into(),
+                value,
+                from,
+                to,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for ReplaceString {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self.value.take_raw_value(memory).unwrap();
+        let from = self.from.take_raw_value(memory).unwrap();
+        let to = self.to.take_raw_value(memory).unwrap();
+
+        let value = value.as_string().unwrap();
+        let from = from.as_string().unwrap();
+        let to = to.as_string().unwrap();
+
+        let result = Value::String(value.replace(&from, &to));
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn replace(value: &str, from: &str, to: &str) -> String {
+        let block = ReplaceString {
+            id: Id::from_str("replace"),
+            value: Value::String(value.to_string()),
+            from: Value::String(from.to_string()),
+            to: Value::String(to.to_string()),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.as_string().unwrap()
+    }
+
+    #[test]
+    fn replaces_every_match() {
+        assert_eq!(replace("a-b-c", "-", "+"), "a+b+c");
+        assert_eq!(replace("엔트리 엔트리", "엔트리", "깃털"), "깃털 깃털");
+        assert_eq!(replace("abc", "x", "y"), "abc");
+    }
+}
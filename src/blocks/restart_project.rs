@@ -0,0 +1,60 @@
+use crate::{
+    code::{Context, Control, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn};
+
+#[derive(Clone)]
+pub(crate) struct RestartProject {
+    id: Id,
+}
+impl Block for RestartProject {
+    fn run(&self, pointer: usize, _memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        ctx.control.push(Control::Restart);
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl RestartProject {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        compiler.push(
+            RestartProject {
+                id: block.id.clone().into(),
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Events;
+
+    use crate::{
+        blocks::{
+            test_world::{change_variable, raw_block, TestWorld},
+            Compiler, Value,
+        },
+        code::Restart,
+    };
+
+    #[test]
+    fn stops_every_runner_and_asks_for_a_restart() {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let repeat = raw_block("repeat_inf", vec![], vec![vec![change_variable("n")]]);
+        let restart = raw_block("restart_project", vec![], vec![]);
+        world.spawn_code(Compiler::compile(&[repeat]));
+        world.spawn_code(Compiler::compile(&[restart, change_variable("n")]));
+
+        world.tick();
+        assert_eq!(world.runners(), 0);
+        assert_eq!(world.number(n), 1.0);
+        assert_eq!(world.resource_mut::<Events<Restart>>().len(), 1);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct ReverseOfString {
+    id: Id,
+    value: Value,
+}
+
+impl ReverseOfString {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        compiler.push(
+            ReverseOfString {
+                id: block.id.clone().into(),
+                value,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for ReverseOfString {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self.value.take_raw_value(memory).unwrap();
+
+        let value = value.as_string().unwrap();
+
+        let result = Value::String(value.chars().rev().collect());
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn reverses_characters() {
+        let block = ReverseOfString {
+            id: Id::from_str("reverse"),
+            value: Value::String("깃털 ab".to_string()),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        assert_eq!(value.as_string(), Ok("ba 털깃".to_string()));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, timed::animate, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct RotateByTime {
+    id: Id,
+    time: Value,
+    angle: Value,
+}
+impl Block for RotateByTime {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let time = memory
+            .cache(&self.id, "time", &self.time)
+            .as_number()
+            .unwrap();
+        let angle = memory
+            .cache(&self.id, "angle", &self.angle)
+            .as_number()
+            .unwrap();
+
+        animate(
+            &self.id,
+            pointer,
+            memory,
+            ctx,
+            time,
+            &["time", "angle"],
+            |share, ctx| {
+                let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+                this.rotation = (this.rotation + share * angle).rem_euclid(360.0);
+            },
+        )
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl RotateByTime {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(time) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        let Some(angle) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        compiler.push(
+            RotateByTime {
+                id: block.id.clone().into(),
+                time,
+                angle,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn rotates_across_ticks() {
+        let mut world = TestWorld::new();
+        world.step = 0.25;
+        let block = RotateByTime {
+            id: Id::from_str("rotate"),
+            time: Value::Number(1.0),
+            angle: Value::Number(-90.0),
+        };
+
+        assert!(world.run(&block, 0).is_continue);
+        assert_eq!(world.this().rotation, 337.5);
+        assert_eq!(world.run_timed(&block), 3);
+        assert_eq!(world.this().rotation, 270.0);
+        assert_eq!(world.this().direction, 90.0);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block};
+
+#[derive(Clone)]
+pub(crate) struct SeeAngleObject {
+    id: Id,
+    target: String,
+}
+
+impl SeeAngleObject {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(target) = compiler.required_param(block, 0) else {
+            return;
+        };
+        let target = target.as_string().unwrap();
+
+        compiler.push(
+            SeeAngleObject {
+                id: block.id.clone().into(),
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+impl Block for SeeAngleObject {
+    fn run(
+        &self,
+        pointer: usize,
+        _memory: &mut crate::code::Memory,
+        ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let target = match &self.target[..] {
+            "mouse" => ctx.mouse.pos,
+            _ => {
+                let id = Id::from_str(&self.target);
+                let entity = ctx.ids.get(&id).unwrap();
+                if entity == ctx.owner {
+                    return super::BlockReturn::basic(pointer);
+                }
+                let target = ctx.objects.get(*entity).unwrap();
+                target.translation.truncate()
+            }
+        };
+
+        let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+        let delta = target - this.translation.truncate();
+        if delta != bevy::prelude::Vec2::ZERO {
+            // Clockwise from up, like Entry's directions.
+            let angle = delta.x.atan2(delta.y).to_degrees();
+            // Turns the picture so that the object's direction points at the target.
+            this.rotation = (angle - this.direction).rem_euclid(360.0);
+        }
+
+        super::BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn see(target: &str) -> SeeAngleObject {
+        SeeAngleObject {
+            id: Id::from_str("see"),
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn faces_the_mouse() {
+        let mut world = TestWorld::new();
+        world.set_mouse(Vec2::new(0.0, 50.0));
+        world.run(&see("mouse"), 0);
+        // Direction 90 is right, so facing up turns the picture 90 degrees back.
+        assert_eq!(world.this().rotation, 270.0);
+
+        world.set_mouse(Vec2::new(-30.0, 0.0));
+        world.run(&see("mouse"), 0);
+        assert_eq!(world.this().rotation, 180.0);
+    }
+
+    #[test]
+    fn faces_another_object() {
+        let mut world = TestWorld::new();
+        world.spawn_object("other", Vec2::new(20.0, -20.0));
+        world.run(&see("other"), 0);
+        assert!((world.this().rotation - 45.0).abs() < 1e-4);
+        world.run(&see("self"), 0);
+        assert!((world.this().rotation - 45.0).abs() < 1e-4);
+    }
+}
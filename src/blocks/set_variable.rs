@@ -3,7 +3,7 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
 
 #[derive(Clone)]
 pub(crate) struct SetVariable {
@@ -28,18 +28,18 @@ impl Block for SetVariable {
     }
 }
 impl SetVariable {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(variable_id) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (variable_id, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        let (value, mut param_blocks) = parse_param(&block.params[1]).unwrap();
-        blocks.append(&mut param_blocks);
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
 
         let variable_id = variable_id.as_string().unwrap();
 
-        blocks.push(
+        compiler.push(
             SetVariable {
                 id: block.id.clone().into(),
                 variable_id,
@@ -47,6 +47,28 @@ impl SetVariable {
             }
             .into(),
         );
-        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn replaces_the_value() {
+        let mut world = TestWorld::new();
+        let variable = world.spawn_variable("v", Value::Number(1.0));
+        let block = SetVariable {
+            id: Id::from_str("set"),
+            variable_id: "v".to_string(),
+            value: Value::String("엔트리".to_string()),
+        };
+
+        world.run(&block, 0);
+        assert_eq!(
+            world.variable(variable).value.as_string().unwrap(),
+            "엔트리"
+        );
     }
 }
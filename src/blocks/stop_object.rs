@@ -0,0 +1,126 @@
+use crate::{
+    code::{Context, Control, Memory, StopTarget},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn};
+
+#[derive(Clone)]
+pub(crate) struct StopObject {
+    id: Id,
+    target: String,
+}
+impl Block for StopObject {
+    fn run(&self, pointer: usize, _memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let target = match &self.target[..] {
+            "all" => StopTarget::All,
+            "thisOnly" => StopTarget::Object(*ctx.owner),
+            "thisThread" => StopTarget::Runner(ctx.runner),
+            "otherThread" => StopTarget::OtherRunners(*ctx.owner, ctx.runner),
+            "ownOthers" => StopTarget::OtherObjects(*ctx.owner),
+            _ => unreachable!(),
+        };
+        ctx.control.push(Control::Stop(target));
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl StopObject {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(target) = compiler.required_param(block, 0) else {
+            return;
+        };
+        let target = target.as_string().unwrap();
+
+        compiler.push(
+            StopObject {
+                id: block.id.clone().into(),
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use dotent::project::script::Param;
+
+    use super::*;
+    use crate::blocks::{
+        test_world::{change_variable, raw_block, TestWorld},
+        BlockVec, Compiler, Value,
+    };
+
+    fn count_forever(variable: &str) -> BlockVec {
+        let repeat = raw_block("repeat_inf", vec![], vec![vec![change_variable(variable)]]);
+        Compiler::compile(&[repeat])
+    }
+
+    /// Runs a loop of `"self"`, a script of `"self"` which stops `target` and then loops, and a
+    /// loop of another object, returning which of the three are still counting.
+    fn survivors(target: &str) -> [bool; 3] {
+        let mut world = TestWorld::new();
+        let other = world.spawn_object("other", Vec2::ZERO);
+        let counters = ["same_object", "stopper", "other_object"]
+            .map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let stop = raw_block(
+            "stop_object",
+            vec![Param::String(target.to_string())],
+            vec![],
+        );
+        let repeat = raw_block("repeat_inf", vec![], vec![vec![change_variable("stopper")]]);
+
+        world.spawn_code(count_forever("same_object"));
+        world.spawn_code(Compiler::compile(&[stop, repeat]));
+        world.spawn_code_of(other, count_forever("other_object"));
+        for _ in 0..5 {
+            world.tick();
+        }
+        counters.map(|counter| world.number(counter) == 5.0)
+    }
+
+    #[test]
+    fn all_stops_every_runner() {
+        assert_eq!(survivors("all"), [false, false, false]);
+    }
+
+    #[test]
+    fn this_only_stops_the_runners_of_the_object() {
+        assert_eq!(survivors("thisOnly"), [false, false, true]);
+    }
+
+    #[test]
+    fn this_thread_stops_only_itself() {
+        assert_eq!(survivors("thisThread"), [true, false, true]);
+    }
+
+    #[test]
+    fn other_thread_stops_the_other_runners_of_the_object() {
+        assert_eq!(survivors("otherThread"), [false, true, true]);
+    }
+
+    #[test]
+    fn own_others_stops_the_runners_of_other_objects() {
+        assert_eq!(survivors("ownOthers"), [true, true, false]);
+    }
+
+    #[test]
+    fn stops_the_running_thread() {
+        let mut world = TestWorld::new();
+        let block = StopObject {
+            id: Id::from_str("stop"),
+            target: "thisThread".to_string(),
+        };
+        world.run(&block, 0);
+        assert!(matches!(
+            world.control[..],
+            [Control::Stop(StopTarget::Runner(runner))] if runner == world.runner
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Label};
+
+#[derive(Clone)]
+pub(crate) struct StopRepeat {
+    id: Id,
+    loop_id: Id,
+    loop_memory: &'static [&'static str],
+    end: Label,
+}
+impl Block for StopRepeat {
+    fn run(&self, _pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        memory.remove_many(&self.loop_id, self.loop_memory);
+        BlockReturn {
+            pointer: self.end.pointer(),
+            is_continue: false,
+            return_value: None,
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.end]
+    }
+}
+impl StopRepeat {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(frame) = compiler.current_loop().cloned() else {
+            warn!("stop_repeat outside of a loop: {}", block.id.as_str());
+            return;
+        };
+
+        compiler.push(
+            StopRepeat {
+                id: block.id.clone().into(),
+                loop_id: frame.id,
+                loop_memory: frame.memory,
+                end: frame.end,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use crate::blocks::{
+        test_world::{change_variable, compare_variable, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn leaves_the_innermost_loop() {
+        let mut world = TestWorld::new();
+        let [n, m, after] =
+            ["n", "m", "after"].map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let stop = raw_block(
+            "_if",
+            vec![compare_variable("n", "EQUAL", 2.0)],
+            vec![vec![raw_block("stop_repeat", vec![], vec![])]],
+        );
+        let inner = raw_block("repeat_inf", vec![], vec![vec![change_variable("n"), stop]]);
+        let outer = raw_block(
+            "repeat_basic",
+            vec![Param::Number(3.0)],
+            vec![vec![inner, change_variable("m")]],
+        );
+        world.spawn_code(Compiler::compile(&[outer, change_variable("after")]));
+
+        for _ in 0..20 {
+            world.tick();
+        }
+        // The inner loop stops once, when `n` is 2, and never again as `n` keeps growing.
+        assert_eq!(world.runners(), 1);
+        assert_eq!(world.number(m), 1.0);
+        assert_eq!(world.number(after), 0.0);
+        assert!(world.number(n) > 2.0);
+    }
+
+    #[test]
+    fn resets_the_loop_for_its_next_run() {
+        let mut world = TestWorld::new();
+        let [n, m] = ["n", "m"].map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let stop = raw_block(
+            "_if",
+            vec![compare_variable("n", "EQUAL", 1.0)],
+            vec![vec![raw_block("stop_repeat", vec![], vec![])]],
+        );
+        let inner = raw_block(
+            "repeat_basic",
+            vec![Param::Number(3.0)],
+            vec![vec![change_variable("n"), stop, change_variable("m")]],
+        );
+        let outer = raw_block("repeat_basic", vec![Param::Number(2.0)], vec![vec![inner]]);
+        world.spawn_code(Compiler::compile(&[outer]));
+
+        for _ in 0..20 {
+            world.tick();
+        }
+        // The first run stops at once; the second counts to 3 again from the start.
+        assert_eq!(world.runners(), 0);
+        assert_eq!(world.number(n), 4.0);
+        assert_eq!(world.number(m), 3.0);
+    }
+
+    #[test]
+    fn is_dropped_outside_of_a_loop() {
+        let blocks = Compiler::compile(&[raw_block("stop_repeat", vec![], vec![])]);
+        assert!(blocks.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::common::Id;
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct Substring {
+    id: Id,
+    value: Value,
+    start: Value,
+    end: Value,
+}
+
+impl Substring {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 1) else {
+            return;
+        };
+
+        let Some(start) = compiler.required_param(block, 3) else {
+            return;
+        };
+
+        let Some(end) = compiler.required_param(block, 5) else {
+            return;
+        };
+
+        compiler.push(
+            Substring {
+                id: block.id.clone().into(),
+                value,
+                start,
+                end,
+            }
+            .into(),
+        );
+    }
+}
+
+fn substring(value: &str, start: f32, end: f32) -> String {
+    let length = value.chars().count() as f32;
+    if start < 0.0 || end < 0.0 || start >= length || end >= length {
+        return String::new();
+    }
+    let (start, end) = (start.min(end) as usize, start.max(end) as usize);
+    value.chars().skip(start).take(end - start + 1).collect()
+}
+
+impl Block for Substring {
+    fn run(
+        &self,
+        pointer: usize,
+        memory: &mut crate::code::Memory,
+        _ctx: &mut crate::code::Context,
+    ) -> super::BlockReturn {
+        let value = self.value.take_raw_value(memory).unwrap();
+        let start = self.start.take_raw_value(memory).unwrap();
+        let end = self.end.take_raw_value(memory).unwrap();
+
+        let value = value.as_string().unwrap();
+        // Like in Entry, non-numeric indexes read as 0, which is out of range.
+        let start = start.as_number().unwrap_or(0.0) - 1.0;
+        let end = end.as_number().unwrap_or(0.0) - 1.0;
+
+        let result = Value::String(substring(&value, start, end));
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(result),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn run(value: &str, start: &str, end: &str) -> String {
+        let block = Substring {
+            id: Id::from_str("substring"),
+            value: Value::String(value.to_string()),
+            start: Value::String(start.to_string()),
+            end: Value::String(end.to_string()),
+        };
+        let value = TestWorld::new().run(&block, 0).return_value.unwrap();
+        value.as_string().unwrap()
+    }
+
+    #[test]
+    fn non_numeric_indexes_are_empty() {
+        assert_eq!(run("gitteol", "1", "3"), "git");
+        assert_eq!(run("gitteol", "abc", "3"), "");
+        assert_eq!(run("gitteol", "1", "abc"), "");
+    }
+
+    #[test]
+    fn substring_is_inclusive() {
+        assert_eq!(substring("gitteol", 0.0, 2.0), "git");
+        assert_eq!(substring("gitteol", 2.0, 0.0), "git");
+    }
+
+    #[test]
+    fn substring_out_of_range_is_empty() {
+        assert_eq!(substring("abc", 0.0, 3.0), "");
+        assert_eq!(substring("abc", -1.0, 1.0), "");
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use bevy::{ecs::system::SystemState, prelude::*};
+use dotent::project::{
+    script::{Block as RawBlock, Param, Script},
+    variable::VariableType,
+};
+
+use crate::{
+    code::{
+        execute_code, Code, CodeRunner, Context, Control, ExecutionBudget, Memory, Queue, Restart,
+        RunnerId,
+    },
+    common::{Id, Ids, LocalPos},
+    debugger::Debugger,
+    event::EventType,
+    function::{CallDepthLimit, Functions},
+    mouse::Mouse,
+    object::Object,
+    random::Random,
+    tick::TickRate,
+    trace::{State, TraceRecorder},
+    variable::Variable,
+};
+
+use super::{Block, BlockReturn, BlockVec, Value};
+
+type ContextParams = (
+    Res<'static, Ids>,
+    Query<'static, 'static, &'static mut Object>,
+    Query<'static, 'static, &'static mut Variable>,
+    Res<'static, Mouse>,
+    ResMut<'static, Random>,
+);
+
+/// A world with just what blocks read through [`Context`], for testing one block at a time, or
+/// whole scripts through [`execute_code`].
+///
+/// Blocks run as the object `"self"`, which starts at the origin.
+pub(crate) struct TestWorld {
+    world: World,
+    state: SystemState<ContextParams>,
+    scheduler: Option<Box<dyn System<In = (), Out = ()>>>,
+    /// Seconds per tick.
+    pub(crate) step: f32,
+    pub(crate) owner: Entity,
+    pub(crate) runner: RunnerId,
+    pub(crate) memory: Memory,
+    pub(crate) params: HashMap<String, Value>,
+    pub(crate) control: Vec<Control>,
+}
+impl TestWorld {
+    pub(crate) fn new() -> Self {
+        let mut world = World::new();
+        world.insert_resource(Ids::new());
+        world.insert_resource(Mouse::default());
+        world.insert_resource(Random::new(Some(0)));
+        world.insert_resource(Queue(Default::default()));
+        world.insert_resource(TickRate::default());
+        world.init_resource::<Functions>();
+        world.init_resource::<CallDepthLimit>();
+        world.init_resource::<ExecutionBudget>();
+        world.init_resource::<Debugger>();
+        world.init_resource::<TraceRecorder>();
+        world.init_resource::<Events<Restart>>();
+        let state = SystemState::new(&mut world);
+
+        let mut test_world = TestWorld {
+            world,
+            state,
+            scheduler: None,
+            step: 1.0 / 60.0,
+            owner: Entity::from_raw(0),
+            runner: RunnerId::next(),
+            memory: Memory::new(),
+            params: HashMap::new(),
+            control: Vec::new(),
+        };
+        test_world.owner = test_world.spawn_object("self", Vec2::ZERO);
+        test_world
+    }
+
+    pub(crate) fn spawn_object(&mut self, id: &str, pos: Vec2) -> Entity {
+        let object = Object {
+            translation: pos.extend(0.0),
+            scale: Vec3::ONE,
+            rotation: 0.0,
+            direction: 90.0,
+            sounds: Vec::new(),
+            text: None,
+        };
+        let entity = self.world.spawn((Id::from_str(id), object)).id();
+        self.world
+            .resource_mut::<Ids>()
+            .insert(Id::from_str(id), entity);
+        entity
+    }
+
+    pub(crate) fn spawn_variable(&mut self, id: &str, value: Value) -> Entity {
+        let variable = Variable {
+            id: Id::from_str(id),
+            variable_type: VariableType::Variable,
+            name: id.to_string(),
+            value,
+            visible: true,
+            pos: LocalPos::new(0.0, 0.0),
+            min_value: 0.0,
+            max_value: 100.0,
+        };
+        let entity = self.world.spawn((Id::from_str(id), variable)).id();
+        self.world
+            .resource_mut::<Ids>()
+            .insert(Id::from_str(id), entity);
+        entity
+    }
+
+    pub(crate) fn object(&self, entity: Entity) -> &Object {
+        self.world.get::<Object>(entity).unwrap()
+    }
+
+    pub(crate) fn object_mut(&mut self, entity: Entity) -> Mut<'_, Object> {
+        self.world.get_mut::<Object>(entity).unwrap()
+    }
+
+    /// Turns the object into a text box showing `text`.
+    pub(crate) fn make_text_box(&mut self, entity: Entity, text: &str) {
+        let text_box = serde_json::from_value(serde_json::json!({ "text": text })).unwrap();
+        self.object_mut(entity).text = Some(text_box);
+    }
+
+    pub(crate) fn this(&self) -> &Object {
+        self.object(self.owner)
+    }
+
+    pub(crate) fn variable(&self, entity: Entity) -> &Variable {
+        self.world.get::<Variable>(entity).unwrap()
+    }
+
+    /// The value of a variable as a number.
+    pub(crate) fn number(&self, variable: Entity) -> f32 {
+        self.variable(variable).value.as_number().unwrap()
+    }
+
+    pub(crate) fn variable_mut(&mut self, entity: Entity) -> Mut<'_, Variable> {
+        self.world.get_mut::<Variable>(entity).unwrap()
+    }
+
+    pub(crate) fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        self.world.resource_mut::<R>()
+    }
+
+    /// The objects and variables as an execution trace records them.
+    pub(crate) fn capture(&mut self) -> State {
+        let (ids, objects, variables, ..) = self.state.get_mut(&mut self.world);
+        State::capture(&ids, &objects, &variables)
+    }
+
+    pub(crate) fn set_mouse(&mut self, pos: Vec2) {
+        self.world.resource_mut::<Mouse>().pos = pos;
+    }
+
+    pub(crate) fn run(&mut self, block: &impl Block, pointer: usize) -> BlockReturn {
+        let (ids, mut objects, mut variables, mouse, mut random) =
+            self.state.get_mut(&mut self.world);
+        let mut ctx = Context {
+            step: self.step,
+            ids: &ids,
+            owner: &self.owner,
+            runner: self.runner,
+            params: &self.params,
+            control: &mut self.control,
+            objects: &mut objects,
+            variables: &mut variables,
+            mouse: &mouse,
+            random: &mut random,
+        };
+        block.run(pointer, &mut self.memory, &mut ctx)
+    }
+
+    /// Runs `block` once per tick until it moves on, returning the ticks it took.
+    pub(crate) fn run_timed(&mut self, block: &impl Block) -> usize {
+        for ticks in 1..=10_000 {
+            if !self.run(block, 0).is_continue {
+                return ticks;
+            }
+        }
+        panic!("block never finished");
+    }
+
+    /// Queues `blocks` as a script of the object `"self"`.
+    pub(crate) fn spawn_code(&mut self, blocks: BlockVec) {
+        self.spawn_code_of(self.owner, blocks);
+    }
+
+    /// Queues `blocks` as a script of `owner`.
+    pub(crate) fn spawn_code_of(&mut self, owner: Entity, blocks: BlockVec) {
+        let code = Code {
+            id: Id::from_str("code"),
+            event: EventType::WhenRunButtonClick,
+            blocks,
+        };
+        let runner = CodeRunner::new(&code, owner);
+        self.world.resource_mut::<Queue>().0.push_back(runner);
+    }
+
+    /// Runs every queued script for one tick.
+    pub(crate) fn tick(&mut self) {
+        let speed = (1.0 / self.step).round() as u32;
+        self.world.resource_mut::<TickRate>().start(speed);
+        let scheduler = self.scheduler.get_or_insert_with(|| {
+            let mut system = IntoSystem::into_system(execute_code);
+            system.initialize(&mut self.world);
+            Box::new(system)
+        });
+        scheduler.run((), &mut self.world);
+        scheduler.apply_buffers(&mut self.world);
+    }
+
+    /// Scripts still queued to run.
+    pub(crate) fn runners(&self) -> usize {
+        self.world.resource::<Queue>().0.len()
+    }
+}
+
+/// A block as it appears in `project.json`, with an id no other raw block has.
+pub(crate) fn raw_block(
+    block_type: &str,
+    params: Vec<Param>,
+    statements: Vec<Vec<RawBlock>>,
+) -> RawBlock {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let id = format!("{}{}", block_type, NEXT.fetch_add(1, Ordering::Relaxed));
+    RawBlock {
+        id: serde_json::from_value(serde_json::json!(id)).unwrap(),
+        x: 0.0,
+        y: 0.0,
+        block_type: block_type.to_string(),
+        params,
+        statements: Script(statements),
+        movable: None,
+        deletable: true,
+        emphasized: false,
+        read_only: None,
+        copyable: true,
+        extensions: Vec::new(),
+    }
+}
+
+/// `change_variable` adding 1 to `variable`.
+pub(crate) fn change_variable(variable: &str) -> RawBlock {
+    raw_block(
+        "change_variable",
+        vec![Param::String(variable.to_string()), Param::Number(1.0)],
+        vec![],
+    )
+}
+
+/// A condition comparing `variable` with `value` using `op`, such as `EQUAL` or `LESS`.
+pub(crate) fn compare_variable(variable: &str, op: &str, value: f32) -> Param {
+    let variable = raw_block(
+        "get_variable",
+        vec![Param::String(variable.to_string())],
+        vec![],
+    );
+    Param::Block(raw_block(
+        "boolean_basic_operator",
+        vec![
+            Param::Block(variable),
+            Param::String(op.to_string()),
+            Param::Number(value),
+        ],
+        vec![],
+    ))
+}
+
+/// The `True` or `False` literal.
+pub(crate) fn literal(value: bool) -> Param {
+    let block_type = if value { "True" } else { "False" };
+    Param::Block(raw_block(block_type, vec![], vec![]))
+}
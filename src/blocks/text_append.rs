@@ -0,0 +1,80 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct TextAppend {
+    id: Id,
+    value: Value,
+}
+impl Block for TextAppend {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let value = self
+            .value
+            .take_raw_value(memory)
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+        if let Some(text_box) = &mut this.text {
+            text_box.text.push_str(&value);
+        }
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl TextAppend {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        compiler.push(
+            TextAppend {
+                id: block.id.clone().into(),
+                value,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn append(world: &mut TestWorld, value: &str) {
+        let block = TextAppend {
+            id: Id::from_str("append"),
+            value: Value::String(value.to_string()),
+        };
+        world.run(&block, 0);
+    }
+
+    #[test]
+    fn adds_to_the_text() {
+        let mut world = TestWorld::new();
+        let owner = world.owner;
+        world.make_text_box(owner, "안녕");
+
+        append(&mut world, " 엔트리");
+        assert_eq!(world.this().text.as_ref().unwrap().text, "안녕 엔트리");
+    }
+
+    #[test]
+    fn sprites_are_left_alone() {
+        let mut world = TestWorld::new();
+
+        append(&mut world, " 엔트리");
+        assert!(world.this().text.is_none());
+    }
+}
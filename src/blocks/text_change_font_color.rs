@@ -0,0 +1,80 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct TextChangeFontColor {
+    id: Id,
+    color: Value,
+}
+impl Block for TextChangeFontColor {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let color = self
+            .color
+            .take_raw_value(memory)
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+        if let Some(text_box) = &mut this.text {
+            text_box.colour = color;
+        }
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl TextChangeFontColor {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(color) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        compiler.push(
+            TextChangeFontColor {
+                id: block.id.clone().into(),
+                color,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn change_font_color(world: &mut TestWorld, color: &str) {
+        let block = TextChangeFontColor {
+            id: Id::from_str("change_font_color"),
+            color: Value::String(color.to_string()),
+        };
+        world.run(&block, 0);
+    }
+
+    #[test]
+    fn changes_the_color() {
+        let mut world = TestWorld::new();
+        let owner = world.owner;
+        world.make_text_box(owner, "안녕");
+
+        change_font_color(&mut world, "#ff0000");
+        assert_eq!(world.this().text.as_ref().unwrap().colour, "#ff0000");
+    }
+
+    #[test]
+    fn sprites_are_left_alone() {
+        let mut world = TestWorld::new();
+
+        change_font_color(&mut world, "#ff0000");
+        assert!(world.this().text.is_none());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct TextPrepend {
+    id: Id,
+    value: Value,
+}
+impl Block for TextPrepend {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let value = self
+            .value
+            .take_raw_value(memory)
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+        if let Some(text_box) = &mut this.text {
+            text_box.text.insert_str(0, &value);
+        }
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl TextPrepend {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        compiler.push(
+            TextPrepend {
+                id: block.id.clone().into(),
+                value,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn prepend(world: &mut TestWorld, value: &str) {
+        let block = TextPrepend {
+            id: Id::from_str("prepend"),
+            value: Value::String(value.to_string()),
+        };
+        world.run(&block, 0);
+    }
+
+    #[test]
+    fn adds_to_the_text() {
+        let mut world = TestWorld::new();
+        let owner = world.owner;
+        world.make_text_box(owner, "안녕");
+
+        prepend(&mut world, "엔트리 ");
+        assert_eq!(world.this().text.as_ref().unwrap().text, "엔트리 안녕");
+    }
+
+    #[test]
+    fn sprites_are_left_alone() {
+        let mut world = TestWorld::new();
+
+        prepend(&mut world, "엔트리 ");
+        assert!(world.this().text.is_none());
+    }
+}
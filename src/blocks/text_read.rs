@@ -0,0 +1,90 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct TextRead {
+    id: Id,
+    target: String,
+}
+impl Block for TextRead {
+    fn run(&self, pointer: usize, _memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let target_entity = match &self.target[..] {
+            "self" => ctx.owner,
+            target => ctx.ids.get(&Id::from_str(target)).unwrap(),
+        };
+        let target = ctx.objects.get(*target_entity).unwrap();
+        let text = target
+            .text
+            .as_ref()
+            .map(|text_box| text_box.text.clone())
+            .unwrap_or_default();
+
+        BlockReturn {
+            pointer: pointer + 1,
+            is_continue: false,
+            return_value: Some(Value::String(text)),
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl TextRead {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(target) = compiler.required_param(block, 0) else {
+            return;
+        };
+        let target = target.as_string().unwrap();
+
+        compiler.push(
+            TextRead {
+                id: block.id.clone().into(),
+                target,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn read(world: &mut TestWorld, target: &str) -> String {
+        let block = TextRead {
+            id: Id::from_str("read"),
+            target: target.to_string(),
+        };
+        let value = world.run(&block, 0).return_value.unwrap();
+        value.as_string().unwrap()
+    }
+
+    #[test]
+    fn reads_itself_or_another_text_box() {
+        let mut world = TestWorld::new();
+        let owner = world.owner;
+        world.make_text_box(owner, "안녕");
+        let other = world.spawn_object("other", Vec2::ZERO);
+        world.make_text_box(other, "엔트리");
+
+        assert_eq!(read(&mut world, "self"), "안녕");
+        assert_eq!(read(&mut world, "other"), "엔트리");
+    }
+
+    #[test]
+    fn sprites_read_as_empty() {
+        let mut world = TestWorld::new();
+        world.spawn_object("sprite", Vec2::ZERO);
+
+        assert_eq!(read(&mut world, "self"), "");
+        assert_eq!(read(&mut world, "sprite"), "");
+    }
+}
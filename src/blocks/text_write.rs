@@ -0,0 +1,80 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+#[derive(Clone)]
+pub(crate) struct TextWrite {
+    id: Id,
+    value: Value,
+}
+impl Block for TextWrite {
+    fn run(&self, pointer: usize, memory: &mut Memory, ctx: &mut Context) -> BlockReturn {
+        let value = self
+            .value
+            .take_raw_value(memory)
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        let mut this = ctx.objects.get_mut(*ctx.owner).unwrap();
+        if let Some(text_box) = &mut this.text {
+            text_box.text = value;
+        }
+
+        BlockReturn::basic(pointer)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+impl TextWrite {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(value) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        compiler.push(
+            TextWrite {
+                id: block.id.clone().into(),
+                value,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    fn write(world: &mut TestWorld, value: &str) {
+        let block = TextWrite {
+            id: Id::from_str("write"),
+            value: Value::String(value.to_string()),
+        };
+        world.run(&block, 0);
+    }
+
+    #[test]
+    fn replaces_the_text() {
+        let mut world = TestWorld::new();
+        let owner = world.owner;
+        world.make_text_box(owner, "안녕");
+
+        write(&mut world, "엔트리");
+        assert_eq!(world.this().text.as_ref().unwrap().text, "엔트리");
+    }
+
+    #[test]
+    fn sprites_are_left_alone() {
+        let mut world = TestWorld::new();
+
+        write(&mut world, "엔트리");
+        assert!(world.this().text.is_none());
+    }
+}
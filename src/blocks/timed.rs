@@ -0,0 +1,50 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{BlockReturn, Value};
+
+/// Runs one tick of a change spread evenly over `time` seconds, like Entry's `*_time` blocks.
+///
+/// The change is split into one share per tick; `apply` gets this tick's share of the whole
+/// change, and the shares add up to 1 on the last tick. The block yields until then, after which
+/// its progress and the `cached` memory labels are cleared so it starts over when run again.
+pub(super) fn animate(
+    id: &Id,
+    pointer: usize,
+    memory: &mut Memory,
+    ctx: &mut Context,
+    time: f32,
+    cached: &[&str],
+    apply: impl FnOnce(f32, &mut Context),
+) -> BlockReturn {
+    let ticks = (time / ctx.step).round().max(1.0);
+    let ticks_left = memory
+        .entry(id, "ticks_left")
+        .or_insert(Value::Number(ticks))
+        .as_number()
+        .unwrap();
+    let remaining = memory
+        .entry(id, "remaining")
+        .or_insert(Value::Number(1.0))
+        .as_number()
+        .unwrap();
+
+    let share = remaining / ticks_left;
+    apply(share, ctx);
+
+    if ticks_left > 1.0 {
+        memory.insert(id, "ticks_left", Value::Number(ticks_left - 1.0));
+        memory.insert(id, "remaining", Value::Number(remaining - share));
+        BlockReturn {
+            pointer,
+            is_continue: true,
+            return_value: None,
+        }
+    } else {
+        memory.remove_many(id, &["ticks_left", "remaining"]);
+        memory.remove_many(id, cached);
+        BlockReturn::basic(pointer)
+    }
+}
@@ -3,7 +3,20 @@ use crate::{
     common::Id,
 };
 
-use super::{parse_param, Block, BlockReturn, BlockVec, Value};
+use super::{compiler::Compiler, Block, BlockReturn, Value};
+
+/// Ticks a wait of `second` takes at `step` seconds per tick, at least one. Counting whole ticks
+/// keeps float error from adding up, and a division within that error of a whole number is
+/// rounded, so waiting 1 second at 0.1 seconds per tick takes 10 ticks, not 11.
+fn wait_ticks(second: f32, step: f32) -> f32 {
+    let ticks = second / step;
+    let ticks = if (ticks - ticks.round()).abs() < 1e-3 {
+        ticks.round()
+    } else {
+        ticks.ceil()
+    };
+    ticks.max(1.0)
+}
 
 #[derive(Clone)]
 pub(crate) struct WaitSecond {
@@ -17,16 +30,16 @@ impl Block for WaitSecond {
             .as_number()
             .unwrap();
 
-        let delta = memory
-            .entry(&self.id, "delta")
-            .or_insert(Value::Number(0.0))
+        let ticks_left = memory
+            .entry(&self.id, "ticks_left")
+            .or_insert(Value::Number(wait_ticks(second, ctx.step)))
             .as_number_mut()
             .unwrap();
 
-        *delta += ctx.time.delta_seconds();
+        *ticks_left -= 1.0;
 
-        if *delta >= second {
-            memory.remove_many(&self.id, &["delta", "second"]);
+        if *ticks_left <= 0.0 {
+            memory.remove_many(&self.id, &["ticks_left", "second"]);
             BlockReturn {
                 pointer: pointer + 1,
                 is_continue: false,
@@ -46,19 +59,60 @@ impl Block for WaitSecond {
     }
 }
 impl WaitSecond {
-    pub(crate) fn build(block: &dotent::project::script::Block) -> BlockVec {
-        let mut blocks: BlockVec = Vec::new();
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let Some(second) = compiler.required_param(block, 0) else {
+            return;
+        };
 
-        let (second, mut param_blocks) = parse_param(&block.params[0]).unwrap();
-        blocks.append(&mut param_blocks);
-
-        blocks.push(
+        compiler.push(
             WaitSecond {
                 id: block.id.clone().into(),
                 second,
             }
             .into(),
         );
-        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_world::TestWorld;
+
+    #[test]
+    fn waits_for_the_given_time() {
+        let block = WaitSecond {
+            id: Id::from_str("wait"),
+            second: Value::Number(1.0),
+        };
+        let mut world = TestWorld::new();
+        world.step = 0.25;
+        assert_eq!(world.run_timed(&block), 4);
+    }
+
+    #[test]
+    fn float_steps_do_not_add_a_tick() {
+        let mut world = TestWorld::new();
+        for (step, second, ticks) in [(0.1, 1.0, 10), (1.0 / 60.0, 1.0, 60), (1.0 / 60.0, 0.5, 30)]
+        {
+            world.step = step;
+            let block = WaitSecond {
+                id: Id::from_str("wait"),
+                second: Value::Number(second),
+            };
+            assert_eq!(
+                world.run_timed(&block),
+                ticks,
+                "{} seconds at {}",
+                second,
+                step
+            );
+        }
+    }
+
+    #[test]
+    fn partial_ticks_round_up() {
+        assert_eq!(wait_ticks(0.3, 0.25), 2.0);
+        assert_eq!(wait_ticks(0.0, 0.25), 1.0);
     }
 }
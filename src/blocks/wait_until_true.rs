@@ -0,0 +1,90 @@
+use crate::{
+    code::{Context, Memory},
+    common::Id,
+};
+
+use super::{compiler::Compiler, Block, BlockReturn, Label, Value};
+
+#[derive(Clone)]
+pub(crate) struct WaitUntilTrue {
+    id: Id,
+    condition: Value,
+    start: Label,
+}
+impl Block for WaitUntilTrue {
+    fn run(&self, pointer: usize, memory: &mut Memory, _ctx: &mut Context) -> BlockReturn {
+        let condition = self
+            .condition
+            .take_raw_value(memory)
+            .is_some_and(|value| value.is_truthy());
+
+        if condition {
+            BlockReturn::basic(pointer)
+        } else {
+            BlockReturn {
+                pointer: self.start.pointer(),
+                is_continue: true,
+                return_value: None,
+            }
+        }
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn labels_mut(&mut self) -> Vec<&mut Label> {
+        vec![&mut self.start]
+    }
+}
+impl WaitUntilTrue {
+    pub(crate) fn build(block: &dotent::project::script::Block, compiler: &mut Compiler) {
+        let start = compiler.label();
+
+        compiler.bind(start);
+        let Some(condition) = compiler.required_param(block, 0) else {
+            return;
+        };
+
+        compiler.push(
+            WaitUntilTrue {
+                id: block.id.clone().into(),
+                condition,
+                start,
+            }
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::{
+        test_world::{change_variable, compare_variable, raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn waits_until_the_condition_holds() {
+        let mut world = TestWorld::new();
+        let [flag, after] =
+            ["flag", "after"].map(|id| world.spawn_variable(id, Value::Number(0.0)));
+        let wait = raw_block(
+            "wait_until_true",
+            vec![compare_variable("flag", "EQUAL", 1.0)],
+            vec![],
+        );
+        world.spawn_code(Compiler::compile(&[wait, change_variable("after")]));
+
+        for _ in 0..3 {
+            world.tick();
+            assert_eq!(world.number(after), 0.0);
+            assert_eq!(world.runners(), 1);
+        }
+
+        world.variable_mut(flag).value = Value::Number(1.0);
+        world.tick();
+        assert_eq!(world.number(after), 1.0);
+        assert_eq!(world.runners(), 0);
+    }
+}
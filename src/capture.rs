@@ -0,0 +1,220 @@
+use std::{
+    num::NonZeroU32,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssets,
+        render_resource::{
+            BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+            ImageDataLayout, MapMode, TextureDescriptor, TextureDimension, TextureFormat,
+            TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        Extract,
+    },
+};
+
+use crate::{
+    stage::{self, StageTransform},
+    tick::TickRate,
+    AppState, GitteolConfig,
+};
+
+/// Renders the window's contents into an image when a capture is requested.
+#[derive(Component)]
+pub(crate) struct CaptureCamera;
+
+/// The image the capture camera renders to, and where to write it this frame.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct CaptureRequest {
+    image: Handle<Image>,
+    path: Option<PathBuf>,
+}
+
+/// Progress of a frame sequence capture.
+#[derive(Resource, Default)]
+pub(crate) struct FrameCapture {
+    frame: u32,
+    done: bool,
+}
+
+fn capture_image(size: Vec2) -> Image {
+    let size = Extent3d {
+        width: size.x.max(1.0) as u32,
+        height: size.y.max(1.0) as u32,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("capture"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::COPY_SRC
+                | TextureUsages::RENDER_ATTACHMENT,
+        },
+        ..Default::default()
+    };
+    image.resize(size);
+    image
+}
+
+pub(crate) fn setup_capture(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    stage: Res<StageTransform>,
+) {
+    let image = images.add(capture_image(stage.window_size()));
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                target: RenderTarget::Image(image.clone()),
+                is_active: false,
+                priority: 1,
+                ..Default::default()
+            },
+            ..stage::stage_camera()
+        },
+        CaptureCamera,
+    ));
+    commands.insert_resource(CaptureRequest { image, path: None });
+}
+
+/// F2 saves a screenshot; `capture_ticks` saves every frame until that many ticks have run.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn capture_system(
+    keys: Res<Input<KeyCode>>,
+    config: Res<GitteolConfig>,
+    state: Res<State<AppState>>,
+    stage: Res<StageTransform>,
+    tick_rate: Res<TickRate>,
+    mut request: ResMut<CaptureRequest>,
+    mut frames: ResMut<FrameCapture>,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<&mut Camera, With<CaptureCamera>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if frames.done {
+        request.path = None;
+        exit.send(AppExit);
+        return;
+    }
+
+    let running = *state.current() == AppState::MainApp;
+    // Nothing will be captured from a project which failed to load.
+    if config.capture_ticks.is_some() && *state.current() == AppState::Error {
+        frames.done = true;
+    }
+    request.path = match config.capture_ticks {
+        Some(ticks) if running => {
+            frames.frame += 1;
+            frames.done = tick_rate.ticks() >= ticks;
+            Some(
+                config
+                    .capture_dir
+                    .join(format!("frame-{:05}.png", frames.frame)),
+            )
+        }
+        _ if keys.just_pressed(KeyCode::F2) => {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            Some(
+                config
+                    .capture_dir
+                    .join(format!("screenshot-{}.png", timestamp)),
+            )
+        }
+        _ => None,
+    };
+
+    let size = stage.window_size();
+    if let Some(image) = images.get_mut(&request.image) {
+        let extent = image.texture_descriptor.size;
+        if request.path.is_some() && Vec2::new(extent.width as f32, extent.height as f32) != size {
+            *image = capture_image(size);
+        }
+    }
+    for mut camera in &mut cameras {
+        camera.is_active = request.path.is_some();
+    }
+}
+
+pub(crate) fn extract_capture_request(
+    mut commands: Commands,
+    request: Extract<Res<CaptureRequest>>,
+) {
+    commands.insert_resource(request.clone());
+}
+
+/// Copies the rendered capture image back from the GPU and writes it as a PNG.
+pub(crate) fn write_capture(
+    request: Option<Res<CaptureRequest>>,
+    gpu_images: Res<RenderAssets<Image>>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+) {
+    let Some(request) = request else {
+        return;
+    };
+    let (Some(path), Some(gpu_image)) = (&request.path, gpu_images.get(&request.image)) else {
+        return;
+    };
+
+    let width = gpu_image.size.x as u32;
+    let height = gpu_image.size.y as u32;
+    let row_bytes = width as usize * 4;
+    let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("capture"),
+        size: (padded_row_bytes * height as usize) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        gpu_image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(padded_row_bytes as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    device.map_buffer(&slice, MapMode::Read, |_| {});
+    device.poll(wgpu::Maintain::Wait);
+    let data: Vec<u8> = slice
+        .get_mapped_range()
+        .chunks(padded_row_bytes)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect();
+    buffer.unmap();
+
+    match image::save_buffer(path, &data, width, height, image::ColorType::Rgba8) {
+        Ok(()) => info!("saved capture to {}", path.display()),
+        Err(err) => error!("cannot save capture to {}: {}", path.display(), err),
+    }
+}
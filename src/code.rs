@@ -1,18 +1,27 @@
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use bevy::prelude::*;
 
 use crate::{
-    blocks::{Block, BlockVec, Value},
+    blocks::{Block, BlockEnum, BlockVec, Value},
     common::{Id, Ids},
+    debugger::{Debugger, Step},
     event::EventType,
+    function::{CallDepthLimit, Function, Functions},
     mouse::Mouse,
     object::Object,
+    random::Random,
+    tick::TickRate,
+    trace::{BlockTrace, State, TraceRecorder},
     variable::Variable,
 };
 
 #[derive(Component)]
 pub(crate) struct Code {
+    pub(crate) id: Id,
     pub(crate) event: EventType,
     pub(crate) blocks: BlockVec,
 }
@@ -23,7 +32,7 @@ pub(crate) struct Codes(pub(crate) Vec<Code>);
 #[derive(Debug)]
 pub(crate) struct Memory(HashMap<String, Value>);
 impl Memory {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Memory(HashMap::new())
     }
 
@@ -43,7 +52,7 @@ impl Memory {
         &mut self,
         block_id: &Id,
         label: &str,
-    ) -> std::collections::hash_map::Entry<String, Value> {
+    ) -> std::collections::hash_map::Entry<'_, String, Value> {
         self.0.entry(Self::format_key(block_id, label))
     }
 
@@ -64,105 +73,475 @@ impl Memory {
     }
 }
 
-pub(crate) struct CodeRunner {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct RunnerId(usize);
+impl RunnerId {
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        RunnerId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+struct Frame {
     code: BlockVec,
     pointer: usize,
-    owner: Id,
     memory: Memory,
+    params: HashMap<String, Value>,
 }
-impl CodeRunner {
-    pub(crate) fn new(code: BlockVec, owner: Id) -> Self {
-        CodeRunner {
+impl Frame {
+    fn new(code: BlockVec, params: HashMap<String, Value>) -> Self {
+        Frame {
             code,
             pointer: 0,
-            owner,
             memory: Memory::new(),
+            params,
+        }
+    }
+
+    fn call(function: &Function, args: Vec<Option<Value>>) -> Self {
+        let params = function
+            .params
+            .iter()
+            .zip(args)
+            .filter_map(|(name, arg)| Some((name.clone()?, arg?)))
+            .collect();
+        Frame::new(function.blocks.clone(), params)
+    }
+}
+
+pub(crate) struct CodeRunner {
+    id: RunnerId,
+    owner: Entity,
+    thread: Id,
+    frames: Vec<Frame>,
+}
+impl CodeRunner {
+    pub(crate) fn new(code: &Code, owner: Entity) -> Self {
+        CodeRunner {
+            id: RunnerId::next(),
+            owner,
+            thread: code.id.clone(),
+            frames: vec![Frame::new(code.blocks.clone(), HashMap::new())],
+        }
+    }
+
+    pub(crate) fn id(&self) -> RunnerId {
+        self.id
+    }
+
+    pub(crate) fn owner(&self) -> Entity {
+        self.owner
+    }
+
+    pub(crate) fn thread(&self) -> &Id {
+        &self.thread
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub(crate) fn pointer(&self) -> Option<usize> {
+        self.frames.last().map(|frame| frame.pointer)
+    }
+
+    pub(crate) fn current_block(&self) -> Option<&BlockEnum> {
+        let frame = self.frames.last()?;
+        frame.code.get(frame.pointer)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum StopTarget {
+    All,
+    Object(Entity),
+    OtherObjects(Entity),
+    Runner(RunnerId),
+    OtherRunners(Entity, RunnerId),
+}
+impl StopTarget {
+    fn matches(&self, runner: RunnerId, owner: Entity) -> bool {
+        match *self {
+            StopTarget::All => true,
+            StopTarget::Object(target) => owner == target,
+            StopTarget::OtherObjects(target) => owner != target,
+            StopTarget::Runner(target) => runner == target,
+            StopTarget::OtherRunners(target, except) => owner == target && runner != except,
         }
     }
 }
 
+#[derive(Clone, Debug)]
+pub(crate) enum Control {
+    Stop(StopTarget),
+    Restart,
+    Call {
+        function: Id,
+        args: Vec<Option<Value>>,
+    },
+}
+
+pub(crate) struct Restart;
+
 pub(crate) struct Context<'a, 'b1, 'c1, 'd1, 'b2, 'c2, 'd2> {
-    pub(crate) time: &'a Res<'a, Time>,
+    /// Seconds of project time that pass in one tick.
+    pub(crate) step: f32,
     pub(crate) ids: &'a Res<'a, Ids>,
     // pub(crate) this: &'a mut Object,
     pub(crate) owner: &'a Entity,
+    pub(crate) runner: RunnerId,
+    pub(crate) params: &'a HashMap<String, Value>,
+    pub(crate) control: &'a mut Vec<Control>,
     pub(crate) objects: &'a mut Query<'b1, 'c1, &'d1 mut Object>,
     pub(crate) variables: &'a mut Query<'b2, 'c2, &'d2 mut Variable>,
     pub(crate) mouse: &'a Res<'a, Mouse>,
+    pub(crate) random: &'a mut Random,
 }
 
 #[derive(Resource)]
 pub(crate) struct Queue(pub(crate) VecDeque<CodeRunner>);
 
+pub(crate) const MAX_BLOCKS_PER_TICK: usize = 100_000;
+
+/// What happens to the runner which uses up the tick's block budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    /// Continues the runner from the same block next tick.
+    Yield,
+    /// Stops the runner.
+    Abort,
+}
+
+/// Watchdog for code that never yields, e.g. deep recursion without any loop or wait.
+///
+/// The budget is shared by every runner in a tick. Once it is used up the rest of the runners
+/// wait for the next tick, ahead of the one which used it up.
+#[derive(Resource)]
+pub(crate) struct ExecutionBudget {
+    pub(crate) blocks_per_tick: usize,
+    pub(crate) on_exceeded: BudgetExceeded,
+}
+impl Default for ExecutionBudget {
+    fn default() -> Self {
+        ExecutionBudget {
+            blocks_per_tick: MAX_BLOCKS_PER_TICK,
+            on_exceeded: BudgetExceeded::Yield,
+        }
+    }
+}
+
+enum Outcome {
+    Yield,
+    Interrupt,
+    Stop,
+}
+
+/// Runs every queued runner until it yields, as one of Entry's ticks.
+///
+/// A runner yields when a block returns with `is_continue`: waiting blocks while they wait, and
+/// the end of every loop once per iteration. Everything else, including function calls, runs
+/// within the same tick.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn execute_code(
     mut queue: ResMut<Queue>,
-    time: Res<Time>,
+    tick_rate: Res<TickRate>,
     ids: Res<Ids>,
     mut objects: Query<&mut Object>,
     mut variables: Query<&mut Variable>,
     mouse: Res<Mouse>,
+    mut random: ResMut<Random>,
+    functions: Res<Functions>,
+    call_depth_limit: Res<CallDepthLimit>,
+    budget: Res<ExecutionBudget>,
+    mut debugger: ResMut<Debugger>,
+    mut trace: ResMut<TraceRecorder>,
+    mut restart: EventWriter<Restart>,
 ) {
+    if debugger.paused && debugger.step.is_none() {
+        return;
+    }
+    let step = debugger.step.take();
+
+    trace.begin_tick(|| State::capture(&ids, &objects, &variables));
+
     let mut new_queue: VecDeque<CodeRunner> = VecDeque::new();
+    let mut control = Vec::new();
+    let mut interrupted = false;
+    let mut executed = 0;
+    let mut exhausted = false;
 
-    while !queue.0.is_empty() {
-        let CodeRunner {
-            mut code,
-            mut pointer,
-            owner,
-            mut memory,
-        } = queue.0.pop_front().unwrap();
-
-        let owner_entity = ids.get(&owner).unwrap();
-
-        let mut ctx = Context {
-            time: &time,
-            ids: &ids,
-            owner: owner_entity,
-            objects: &mut objects,
-            variables: &mut variables,
-            mouse: &mouse,
-        };
+    while let Some(mut runner) = queue.0.pop_front() {
+        let id = runner.id;
+        let owner = runner.owner;
+        let mut ran = 0;
 
-        while let Some(block) = code.get_mut(pointer) {
-            let prev_pointer = pointer;
+        let outcome = loop {
+            let depth = runner.frames.len();
+            let Some(frame) = runner.frames.last_mut() else {
+                break Outcome::Stop;
+            };
+            let Some(block) = frame.code.get_mut(frame.pointer) else {
+                runner.frames.pop();
+                continue;
+            };
 
-            let block_return = block.run(pointer, &mut memory, &mut ctx);
-            pointer = block_return.pointer;
-            if let Some(return_value) = block_return.return_value {
-                memory.insert(block.get_id(), "return_value", return_value);
+            if executed >= budget.blocks_per_tick {
+                exhausted = true;
+                if ran == 0 {
+                    // Someone else used up the budget, so this runner goes first next tick.
+                    break Outcome::Interrupt;
+                }
+                warn!(
+                    "{:?}::{} used up the budget of {} blocks per tick",
+                    owner, runner.thread.0, budget.blocks_per_tick
+                );
+                match budget.on_exceeded {
+                    BudgetExceeded::Yield => break Outcome::Yield,
+                    BudgetExceeded::Abort => break Outcome::Stop,
+                }
             }
 
-            {
-                let block_name = block.to_string();
-                let block_id = block.get_id().clone().0;
-                let code_id = code
-                    .first()
-                    .map(|c| c.get_id().0.clone())
-                    .unwrap_or_else(|| "None".to_string());
-                let conti = if block_return.is_continue { "|" } else { "-" };
-                info!(
-                    "{:?}::{}::{}::{: <18} [{: >2} {}> {: >2}] Memory {:?}",
-                    owner_entity,
-                    code_id,
-                    block_id,
-                    block_name,
-                    prev_pointer,
-                    conti,
-                    pointer,
-                    memory.0
+            if debugger.hits_breakpoint(id, frame.pointer, block.get_id()) {
+                interrupted = true;
+                break Outcome::Interrupt;
+            }
+
+            let before = trace
+                .is_enabled()
+                .then(|| State::capture(&ids, &objects, &variables));
+
+            let mut ctx = Context {
+                step: tick_rate.step(),
+                ids: &ids,
+                owner: &owner,
+                runner: id,
+                params: &frame.params,
+                control: &mut control,
+                objects: &mut objects,
+                variables: &mut variables,
+                mouse: &mouse,
+                random: &mut random,
+            };
+
+            let prev_pointer = frame.pointer;
+
+            let block_return = block.run(frame.pointer, &mut frame.memory, &mut ctx);
+            frame.pointer = block_return.pointer;
+            executed += 1;
+            ran += 1;
+
+            if let Some(before) = before {
+                let after = State::capture(&ids, &objects, &variables);
+                let tick = trace.tick();
+                trace.record(
+                    BlockTrace {
+                        tick,
+                        runner: format!("{:?}", id),
+                        depth,
+                        block_id: block.get_id().0.clone(),
+                        block_type: block.to_string(),
+                        from: prev_pointer,
+                        to: frame.pointer,
+                        yielded: block_return.is_continue,
+                        return_value: block_return.return_value.as_ref().and_then(Into::into),
+                        delta: State::default(),
+                    },
+                    &before,
+                    &after,
                 );
             }
 
+            if let Some(return_value) = block_return.return_value {
+                frame
+                    .memory
+                    .insert(block.get_id(), "return_value", return_value);
+            }
+
+            let mut stopped = false;
+            for control in control.drain(..) {
+                match control {
+                    Control::Stop(target) => {
+                        queue
+                            .0
+                            .retain(|runner| !target.matches(runner.id, runner.owner));
+                        new_queue.retain(|runner| !target.matches(runner.id, runner.owner));
+                        stopped |= target.matches(id, owner);
+                    }
+                    Control::Restart => {
+                        queue.0.clear();
+                        new_queue.clear();
+                        restart.send(Restart);
+                        stopped = true;
+                    }
+                    Control::Call { function, args } => match functions.get(&function) {
+                        Some(_) if depth >= call_depth_limit.0 => {
+                            warn!(
+                                "maximum function call depth {} exceeded: {}",
+                                call_depth_limit.0, function.0
+                            );
+                            stopped = true;
+                        }
+                        Some(function) => runner.frames.push(Frame::call(function, args)),
+                        None => warn!("unknown function: {}", function.0),
+                    },
+                }
+            }
+            if step == Some(Step::Block) {
+                interrupted = true;
+            }
+
+            if stopped {
+                break Outcome::Stop;
+            }
+
             if block_return.is_continue {
-                new_queue.push_back(CodeRunner {
-                    code,
-                    pointer,
-                    owner,
-                    memory,
-                });
-                break;
+                break Outcome::Yield;
             }
+
+            if interrupted {
+                break Outcome::Interrupt;
+            }
+        };
+
+        match outcome {
+            Outcome::Yield => new_queue.push_back(runner),
+            Outcome::Interrupt => queue.0.push_front(runner),
+            Outcome::Stop => {}
         }
+
+        if interrupted || exhausted {
+            break;
+        }
+    }
+    queue.0.append(&mut new_queue);
+    trace.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use super::*;
+    use crate::blocks::{
+        test_world::{change_variable as change, literal, raw_block, TestWorld},
+        Compiler,
+    };
+
+    fn count(world: &TestWorld, variable: Entity) -> f32 {
+        world.variable(variable).value.as_number().unwrap()
+    }
+
+    fn budget(world: &mut TestWorld, blocks_per_tick: usize, on_exceeded: BudgetExceeded) {
+        *world.resource_mut::<ExecutionBudget>() = ExecutionBudget {
+            blocks_per_tick,
+            on_exceeded,
+        };
+    }
+
+    #[test]
+    fn code_without_loops_runs_in_one_tick() {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        world.spawn_code(Compiler::compile(&vec![change("n"); 5]));
+
+        world.tick();
+        assert_eq!(count(&world, n), 5.0);
+        assert_eq!(world.runners(), 0);
+    }
+
+    #[test]
+    fn loops_yield_once_per_iteration() {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let repeat = raw_block(
+            "repeat_basic",
+            vec![Param::Number(3.0)],
+            vec![vec![change("n"), change("n")]],
+        );
+        world.spawn_code(Compiler::compile(&[repeat, change("n")]));
+
+        for expected in [2.0, 4.0, 6.0] {
+            world.tick();
+            assert_eq!(count(&world, n), expected);
+            assert_eq!(world.runners(), 1);
+        }
+        world.tick();
+        assert_eq!(count(&world, n), 7.0);
+        assert_eq!(world.runners(), 0);
+    }
+
+    #[test]
+    fn loops_without_waiting_blocks_still_yield() {
+        let mut world = TestWorld::new();
+        // Aborts the runner if an iteration ever runs without yielding.
+        budget(&mut world, 10, BudgetExceeded::Abort);
+        let empty = raw_block("repeat_inf", vec![], vec![]);
+        let values = raw_block(
+            "repeat_while_true",
+            vec![literal(true), Param::String("while".to_string())],
+            vec![],
+        );
+        world.spawn_code(Compiler::compile(&[empty]));
+        world.spawn_code(Compiler::compile(&[values]));
+
+        for _ in 0..100 {
+            world.tick();
+        }
+        assert_eq!(world.runners(), 2);
+    }
+
+    #[test]
+    fn exhausted_budget_yields_until_the_next_tick() {
+        let mut world = TestWorld::new();
+        budget(&mut world, 4, BudgetExceeded::Yield);
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        world.spawn_code(Compiler::compile(&vec![change("n"); 10]));
+
+        for expected in [4.0, 8.0, 10.0] {
+            world.tick();
+            assert_eq!(count(&world, n), expected);
+        }
+        assert_eq!(world.runners(), 0);
+    }
+
+    #[test]
+    fn exhausted_budget_aborts_the_runner() {
+        let mut world = TestWorld::new();
+        budget(&mut world, 4, BudgetExceeded::Abort);
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        world.spawn_code(Compiler::compile(&vec![change("n"); 10]));
+
+        world.tick();
+        assert_eq!(count(&world, n), 4.0);
+        assert_eq!(world.runners(), 0);
+
+        // Only the runner which used up the budget is stopped.
+        world.spawn_code(Compiler::compile(&vec![change("n"); 4]));
+        world.spawn_code(Compiler::compile(&[change("n")]));
+        world.tick();
+        assert_eq!(count(&world, n), 8.0);
+        assert_eq!(world.runners(), 1);
+        world.tick();
+        assert_eq!(count(&world, n), 9.0);
+        assert_eq!(world.runners(), 0);
+    }
+
+    #[test]
+    fn budget_is_shared_by_every_runner() {
+        let mut world = TestWorld::new();
+        budget(&mut world, 4, BudgetExceeded::Yield);
+        let a = world.spawn_variable("a", Value::Number(0.0));
+        let b = world.spawn_variable("b", Value::Number(0.0));
+        world.spawn_code(Compiler::compile(&vec![change("a"); 6]));
+        world.spawn_code(Compiler::compile(&vec![change("b"); 6]));
+
+        world.tick();
+        assert_eq!((count(&world, a), count(&world, b)), (4.0, 0.0));
+        // The runner which was left waiting goes first.
+        world.tick();
+        assert_eq!((count(&world, a), count(&world, b)), (4.0, 4.0));
+        world.tick();
+        assert_eq!((count(&world, a), count(&world, b)), (6.0, 6.0));
+        assert_eq!(world.runners(), 0);
     }
-    queue.0 = new_queue;
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use bevy::prelude::*;
 
+use crate::stage::StageTransform;
+
 #[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct Id(pub(crate) String);
 
@@ -22,8 +24,12 @@ impl LocalPos {
     pub(crate) fn new(x: f32, y: f32) -> Self {
         Self(x, y)
     }
-    pub(crate) fn to_variable_pos(&self) -> (f32, f32) {
-        (self.0 + 240.0, self.1 + 135.0 - 9.0)
+    pub(crate) fn as_vec2(&self) -> Vec2 {
+        Vec2::new(self.0, self.1)
+    }
+    pub(crate) fn to_variable_pos(&self, stage: &StageTransform) -> (f32, f32) {
+        let pos = stage.stage_to_ui(Vec2::new(self.0, self.1 - 9.0));
+        (pos.x, pos.y)
     }
 }
 
@@ -42,4 +48,12 @@ impl Ids {
     pub(crate) fn get(&self, id: &Id) -> Option<&Entity> {
         self.0.get(id)
     }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Id, &Entity)> {
+        self.0.iter()
+    }
 }
@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::{
+    blocks::Block,
+    code::{Queue, RunnerId},
+    common::Id,
+    object::Object,
+    variable::Variable,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Step {
+    Block,
+    Tick,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct Debugger {
+    pub(crate) visible: bool,
+    pub(crate) paused: bool,
+    pub(crate) step: Option<Step>,
+    pub(crate) breakpoints: HashSet<Id>,
+    resume_from: Option<(RunnerId, usize)>,
+}
+impl Debugger {
+    pub(crate) fn new(breakpoints: &[String]) -> Self {
+        Debugger {
+            breakpoints: breakpoints.iter().map(|id| Id::from_str(id)).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Pauses before a block with a breakpoint, letting it run once the runner is resumed.
+    pub(crate) fn hits_breakpoint(&mut self, runner: RunnerId, pointer: usize, block: &Id) -> bool {
+        if !self.breakpoints.contains(block) {
+            return false;
+        }
+        if self.resume_from == Some((runner, pointer)) {
+            self.resume_from = None;
+            return false;
+        }
+        self.paused = true;
+        self.resume_from = Some((runner, pointer));
+        true
+    }
+
+    fn toggle_breakpoint(&mut self, block: &Id) {
+        if !self.breakpoints.remove(block) {
+            self.breakpoints.insert(block.clone());
+        }
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct DebuggerUi;
+
+pub(crate) fn setup_debugger(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/NanumGothic.ttf");
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    ..Default::default()
+                },
+                max_size: Size::new(Val::Percent(50.0), Val::Percent(100.0)),
+                display: Display::None,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.7).into(),
+            ..Default::default()
+        })
+        .insert(DebuggerUi)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Px(4.0)),
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: 12.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn debugger_input_system(
+    keys: Res<Input<KeyCode>>,
+    queue: Res<Queue>,
+    mut debugger: ResMut<Debugger>,
+) {
+    if keys.just_pressed(KeyCode::F12) {
+        debugger.visible = !debugger.visible;
+    }
+    if keys.just_pressed(KeyCode::F5) {
+        debugger.paused = !debugger.paused;
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        if let Some(block) = queue.0.front().and_then(|runner| runner.current_block()) {
+            debugger.toggle_breakpoint(block.get_id());
+        }
+    }
+    if keys.just_pressed(KeyCode::F10) {
+        debugger.paused = true;
+        debugger.step = Some(Step::Block);
+    }
+    if keys.just_pressed(KeyCode::F11) {
+        debugger.paused = true;
+        debugger.step = Some(Step::Tick);
+    }
+}
+
+pub(crate) fn debugger_ui_system(
+    debugger: Res<Debugger>,
+    queue: Res<Queue>,
+    ids: Query<&Id>,
+    objects: Query<(&Id, &Object)>,
+    variables: Query<&Variable>,
+    mut uis: Query<(&mut Style, &Children), With<DebuggerUi>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (mut style, children) in uis.iter_mut() {
+        if !debugger.visible {
+            style.display = Display::None;
+            continue;
+        }
+        style.display = Display::Flex;
+
+        let mut lines = vec![
+            format!(
+                "{} | F5 pause/resume  F9 breakpoint  F10 step block  F11 step tick",
+                if debugger.paused { "PAUSED" } else { "RUNNING" }
+            ),
+            String::new(),
+            "Runners".to_string(),
+        ];
+        for runner in queue.0.iter() {
+            let owner = ids
+                .get(runner.owner())
+                .map(|id| id.0.clone())
+                .unwrap_or_default();
+            let (block_type, block_id) = runner
+                .current_block()
+                .map(|block| (block.to_string(), block.get_id().0.clone()))
+                .unwrap_or_default();
+            let breakpoint = if debugger.breakpoints.contains(&Id::from_str(&block_id)) {
+                "*"
+            } else {
+                " "
+            };
+            lines.push(format!(
+                "{}{:?} {} {} #{} @{} {} {}",
+                breakpoint,
+                runner.id(),
+                owner,
+                runner.thread().0,
+                runner.depth(),
+                runner.pointer().unwrap_or_default(),
+                block_type,
+                block_id
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Variables".to_string());
+        for variable in variables.iter() {
+            lines.push(format!(
+                "{} = {}",
+                variable.name,
+                variable.value.as_string().unwrap_or_default()
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Objects".to_string());
+        for (id, object) in objects.iter() {
+            lines.push(format!(
+                "{} x: {:.2} y: {:.2} scale: {:.2}, {:.2}",
+                id.0, object.translation.x, object.translation.y, object.scale.x, object.scale.y
+            ));
+        }
+
+        if let Ok(mut text) = texts.get_mut(children[0]) {
+            text.sections[0].value = lines.join("\n");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use super::*;
+    use crate::blocks::{
+        test_world::{raw_block, TestWorld},
+        Compiler, Value,
+    };
+
+    #[test]
+    fn breakpoints_pause_once_per_visit() {
+        let mut debugger = Debugger::default();
+        let runner = RunnerId::next();
+        let block = Id::from_str("block");
+        debugger.breakpoints.insert(block.clone());
+
+        assert!(!debugger.hits_breakpoint(runner, 0, &Id::from_str("other")));
+        assert!(debugger.hits_breakpoint(runner, 3, &block));
+        assert!(debugger.paused);
+        // Resuming runs the block it stopped at.
+        debugger.paused = false;
+        assert!(!debugger.hits_breakpoint(runner, 3, &block));
+        // The next visit, e.g. in a loop, stops again.
+        assert!(debugger.hits_breakpoint(runner, 3, &block));
+        assert!(debugger.hits_breakpoint(RunnerId::next(), 3, &block));
+    }
+
+    fn counting_world(blocks: usize) -> (TestWorld, Entity, Vec<Id>) {
+        let mut world = TestWorld::new();
+        let n = world.spawn_variable("n", Value::Number(0.0));
+        let changes: Vec<_> = (0..blocks)
+            .map(|_| {
+                raw_block(
+                    "change_variable",
+                    vec![Param::String("n".to_string()), Param::Number(1.0)],
+                    vec![],
+                )
+            })
+            .collect();
+        let blocks = Compiler::compile(&changes);
+        let ids = blocks.iter().map(|block| block.get_id().clone()).collect();
+        world.spawn_code(blocks);
+        (world, n, ids)
+    }
+
+    fn count(world: &TestWorld, n: Entity) -> f32 {
+        world.variable(n).value.as_number().unwrap()
+    }
+
+    #[test]
+    fn steps_one_block_or_one_tick() {
+        let (mut world, n, _) = counting_world(3);
+        world.resource_mut::<Debugger>().paused = true;
+        world.tick();
+        assert_eq!(count(&world, n), 0.0);
+
+        world.resource_mut::<Debugger>().step = Some(Step::Block);
+        world.tick();
+        assert_eq!(count(&world, n), 1.0);
+        world.tick();
+        assert_eq!(count(&world, n), 1.0);
+
+        world.resource_mut::<Debugger>().step = Some(Step::Tick);
+        world.tick();
+        assert_eq!(count(&world, n), 3.0);
+        assert!(world.resource_mut::<Debugger>().paused);
+        assert_eq!(world.runners(), 0);
+    }
+
+    #[test]
+    fn resumes_from_a_breakpoint() {
+        let (mut world, n, ids) = counting_world(3);
+        world
+            .resource_mut::<Debugger>()
+            .breakpoints
+            .insert(ids[1].clone());
+
+        world.tick();
+        assert_eq!(count(&world, n), 1.0);
+        assert!(world.resource_mut::<Debugger>().paused);
+
+        world.resource_mut::<Debugger>().step = Some(Step::Block);
+        world.tick();
+        assert_eq!(count(&world, n), 2.0);
+
+        world.resource_mut::<Debugger>().paused = false;
+        world.tick();
+        assert_eq!(count(&world, n), 3.0);
+        assert_eq!(world.runners(), 0);
+    }
+}
@@ -3,7 +3,6 @@ use strum::EnumString;
 
 use crate::{
     code::{CodeRunner, Codes, Queue},
-    common::Id,
     object::Object,
 };
 
@@ -20,15 +19,13 @@ pub(crate) struct Event {
 pub(crate) fn event_listener(
     mut events: EventReader<Event>,
     mut queue: ResMut<Queue>,
-    codes_query: Query<(&Codes, &Id), With<Object>>,
+    codes_query: Query<(Entity, &Codes), With<Object>>,
 ) {
     for event in events.iter() {
-        for (codes, id) in codes_query.iter() {
+        for (entity, codes) in codes_query.iter() {
             for code in &codes.0 {
                 if code.event == event.event_type {
-                    queue
-                        .0
-                        .push_back(CodeRunner::new(code.blocks.clone(), id.clone()));
+                    queue.0.push_back(CodeRunner::new(code, entity));
                 }
             }
         }
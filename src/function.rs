@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use dotent::project::script::Param;
+
+use crate::{
+    blocks::{BlockVec, Compiler},
+    common::Id,
+};
+
+const MAX_CALL_DEPTH: usize = 1024;
+
+pub(crate) struct Function {
+    pub(crate) params: Vec<Option<String>>,
+    pub(crate) blocks: BlockVec,
+}
+impl From<&dotent::project::function::Function> for Function {
+    fn from(value: &dotent::project::function::Function) -> Self {
+        let mut params = Vec::new();
+        let mut compiler = Compiler::new();
+
+        if let Some(code) = value.content.0.first() {
+            if let Some(definition) = code.first() {
+                if let Some(fields) = definition.params.first() {
+                    parse_fields(fields, &mut params);
+                }
+                compiler.statements(&definition.statements, 0);
+            }
+            for raw_block in code.iter().skip(1) {
+                compiler.block(raw_block);
+            }
+        }
+
+        Function {
+            params,
+            blocks: compiler.finish(),
+        }
+    }
+}
+
+/// Collects the parameters of a function's fields in order. Labels are only part of the
+/// template, so they have no argument in `func_*` blocks.
+fn parse_fields(param: &Param, params: &mut Vec<Option<String>>) {
+    let Param::Block(field) = param else {
+        return;
+    };
+    match &field.block_type[..] {
+        "function_field_label" => {}
+        "function_field_string" | "function_field_number" | "function_field_boolean" => match field
+            .params
+            .first()
+        {
+            Some(Param::Block(param_block)) => params.push(Some(param_block.block_type.clone())),
+            _ => params.push(None),
+        },
+        _ => {
+            warn!("unsupported function field: {}", field.block_type);
+            return;
+        }
+    }
+    if let Some(next) = field.params.get(1) {
+        parse_fields(next, params);
+    }
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct Functions(HashMap<Id, Function>);
+impl Functions {
+    pub(crate) fn new(functions: &[dotent::project::function::Function]) -> Self {
+        Functions(
+            functions
+                .iter()
+                .map(|function| (function.id.clone().into(), function.into()))
+                .collect(),
+        )
+    }
+
+    pub(crate) fn get(&self, id: &Id) -> Option<&Function> {
+        self.0.get(id)
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct CallDepthLimit(pub(crate) usize);
+impl Default for CallDepthLimit {
+    fn default() -> Self {
+        CallDepthLimit(MAX_CALL_DEPTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::{Param, Script};
+
+    use super::*;
+    use crate::blocks::{
+        test_world::{raw_block, TestWorld},
+        Value,
+    };
+
+    fn define(world: &mut TestWorld, id: &str, params: &[&str], blocks: BlockVec) {
+        let function = Function {
+            params: params.iter().map(|param| Some(param.to_string())).collect(),
+            blocks,
+        };
+        world
+            .resource_mut::<Functions>()
+            .0
+            .insert(Id::from_str(id), function);
+    }
+
+    fn set(variable: &str, value: Param) -> dotent::project::script::Block {
+        raw_block(
+            "set_variable",
+            vec![Param::String(variable.to_string()), value],
+            vec![],
+        )
+    }
+
+    fn param(name: &str) -> Param {
+        Param::Block(raw_block(name, vec![], vec![]))
+    }
+
+    #[test]
+    fn labels_take_no_argument() {
+        let mut world = TestWorld::new();
+        let text = world.spawn_variable("text", Value::Number(1.0));
+        let string_field = raw_block(
+            "function_field_string",
+            vec![param("stringParam_a"), Param::Null],
+            vec![],
+        );
+        let label = raw_block(
+            "function_field_label",
+            vec![Param::String("say".to_string()), Param::Block(string_field)],
+            vec![],
+        );
+        let definition = raw_block(
+            "function_create",
+            vec![Param::Block(label)],
+            vec![vec![set("text", param("stringParam_a"))]],
+        );
+        let raw = dotent::project::function::Function {
+            id: serde_json::from_value(serde_json::json!("f")).unwrap(),
+            content: Script(vec![vec![definition]]),
+        };
+        let function = Function::from(&raw);
+        assert_eq!(function.params, [Some("stringParam_a".to_string())]);
+        world
+            .resource_mut::<Functions>()
+            .0
+            .insert(Id::from_str("f"), function);
+
+        let args = vec![Param::String("hi".to_string())];
+        world.spawn_code(Compiler::compile(&[raw_block("func_f", args, vec![])]));
+        world.tick();
+        assert_eq!(world.variable(text).value.as_string(), Ok("hi".to_string()));
+    }
+
+    #[test]
+    fn missing_arguments_are_empty() {
+        let mut world = TestWorld::new();
+        let text = world.spawn_variable("text", Value::Number(1.0));
+        let flag = world.spawn_variable("flag", Value::Bool(true));
+        let blocks = Compiler::compile(&[
+            set("text", param("stringParam_a")),
+            set("flag", param("booleanParam_b")),
+        ]);
+        define(
+            &mut world,
+            "f",
+            &["stringParam_a", "booleanParam_b"],
+            blocks,
+        );
+
+        world.spawn_code(Compiler::compile(&[raw_block("func_f", vec![], vec![])]));
+        world.tick();
+        assert_eq!(world.variable(text).value.as_string(), Ok(String::new()));
+        assert!(!world.variable(flag).value.is_truthy());
+
+        let args = vec![Param::String("hi".to_string())];
+        world.spawn_code(Compiler::compile(&[raw_block("func_f", args, vec![])]));
+        world.tick();
+        assert_eq!(world.variable(text).value.as_string(), Ok("hi".to_string()));
+        assert!(!world.variable(flag).value.is_truthy());
+    }
+
+    #[test]
+    fn recursion_stops_at_the_depth_limit() {
+        let mut world = TestWorld::new();
+        world.resource_mut::<CallDepthLimit>().0 = 10;
+        let count = world.spawn_variable("count", Value::Number(0.0));
+        let blocks = Compiler::compile(&[
+            raw_block(
+                "change_variable",
+                vec![Param::String("count".to_string()), Param::Number(1.0)],
+                vec![],
+            ),
+            raw_block("func_g", vec![], vec![]),
+        ]);
+        define(&mut world, "g", &[], blocks);
+
+        world.spawn_code(Compiler::compile(&[raw_block("func_g", vec![], vec![])]));
+        world.tick();
+        // The script itself is the first frame, so nine calls fit under the limit.
+        assert_eq!(world.variable(count).value.as_number(), Ok(9.0));
+        assert_eq!(world.runners(), 0);
+    }
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{ScheduleRunnerPlugin, ScheduleRunnerSettings},
+    prelude::*,
+    window::WindowId,
+};
+
+/// Runs the app without a display, e.g. to capture frames with a software renderer.
+///
+/// Add it after `DefaultPlugins` with `WinitPlugin` disabled and without a primary window. It
+/// adds a primary window that is never presented, so the stage and UI are laid out as usual.
+pub struct HeadlessPlugin {
+    pub window_size: Vec2,
+}
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        let descriptor = WindowDescriptor {
+            width: self.window_size.x,
+            height: self.window_size.y,
+            ..Default::default()
+        };
+        app.world.resource_mut::<Windows>().add(Window::new(
+            WindowId::primary(),
+            &descriptor,
+            self.window_size.x as u32,
+            self.window_size.y as u32,
+            1.0,
+            None,
+            None,
+        ));
+        app.insert_resource(ScheduleRunnerSettings::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugin(ScheduleRunnerPlugin);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{collections::VecDeque, path::PathBuf};
+
+use asset::{EntryAssetLoader, EntryFile, EntryProject, ProjectImages, ProjectJsonTask};
+use bevy::{
+    prelude::*,
+    render::{RenderApp, RenderStage},
+};
+
+mod asset;
+mod blocks;
+mod capture;
+mod code;
+mod common;
+mod debugger;
+mod event;
+mod function;
+mod headless;
+mod loading;
+mod mouse;
+mod object;
+mod random;
+mod reload;
+mod snapshot;
+mod stage;
+mod text_box;
+mod tick;
+mod trace;
+mod variable;
+
+use capture::FrameCapture;
+use code::{ExecutionBudget, Queue, Restart};
+use common::{Id, Ids};
+use debugger::Debugger;
+use event::{Event, EventType};
+use function::{CallDepthLimit, Functions};
+use loading::{ErrorUi, LoadError, LoadingProgress, LoadingUi};
+use mouse::Mouse;
+use object::{spawn_objects, Object};
+use random::Random;
+use reload::PreservedVariables;
+use stage::StageTransform;
+use tick::TickRate;
+use trace::TraceRecorder;
+use variable::{spawn_variable, Variable, VariableUiRoot};
+
+pub use code::BudgetExceeded;
+pub use headless::HeadlessPlugin;
+pub use trace::replay_report;
+
+/// Where the project is loaded from and how the stage is laid out on screen.
+#[derive(Resource, Clone, Debug)]
+pub struct GitteolConfig {
+    /// Path of the project relative to the asset folder: a `.ent` file, a `project.json`, or a
+    /// directory with an unpacked `.ent`.
+    pub project_path: String,
+    /// Size of Entry's stage in stage units.
+    pub stage_size: Vec2,
+    /// Screen pixels per stage unit of the initial window.
+    /// The stage is rescaled and letterboxed to fit when the window is resized.
+    pub scale: f32,
+    /// Seed of the random number blocks, or `None` to seed from the clock.
+    pub seed: Option<u64>,
+    /// Keeps variable values when the project is reloaded after the `.ent` file changes.
+    pub preserve_variables: bool,
+    /// Directory where Ctrl+S saves snapshots of the running project.
+    pub snapshot_dir: PathBuf,
+    /// Directory where F2 screenshots and captured frames are written.
+    pub capture_dir: PathBuf,
+    /// Captures every frame to `capture_dir` until this many ticks have run, then exits.
+    pub capture_ticks: Option<u64>,
+    /// Ticks per second, or `None` to use the project's speed.
+    pub tick_rate: Option<u32>,
+    /// How many missed ticks may run in one frame after the app stalls; the rest are skipped.
+    pub max_ticks_per_frame: u32,
+    /// Blocks all code may run in one tick before the budget is used up.
+    pub blocks_per_tick: usize,
+    /// What happens to code which uses up the budget.
+    pub on_budget_exceeded: BudgetExceeded,
+    /// File to write the execution trace to, if any.
+    pub trace_path: Option<PathBuf>,
+    /// Ids of the blocks to pause before.
+    pub breakpoints: Vec<String>,
+}
+impl Default for GitteolConfig {
+    fn default() -> Self {
+        Self {
+            project_path: "project.ent".to_string(),
+            stage_size: Vec2::new(480.0, 270.0),
+            scale: 1.0,
+            seed: None,
+            preserve_variables: false,
+            snapshot_dir: PathBuf::from("."),
+            capture_dir: PathBuf::from("."),
+            capture_ticks: None,
+            tick_rate: None,
+            max_ticks_per_frame: tick::DEFAULT_MAX_TICKS_PER_FRAME,
+            blocks_per_tick: code::MAX_BLOCKS_PER_TICK,
+            on_budget_exceeded: BudgetExceeded::Yield,
+            trace_path: None,
+            breakpoints: Vec::new(),
+        }
+    }
+}
+impl GitteolConfig {
+    /// The window size which fits the stage at the configured scale.
+    pub fn window_size(&self) -> Vec2 {
+        self.stage_size * self.scale
+    }
+}
+
+/// The Entry player: loads the configured project and runs it.
+///
+/// Needs Bevy's `DefaultPlugins` (or equivalent window, asset, render and UI plugins).
+/// A `.ent` project is reloaded when it changes if the `AssetPlugin` watches for changes.
+#[derive(Default)]
+pub struct GitteolPlugin {
+    pub config: GitteolConfig,
+}
+
+impl Plugin for GitteolPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone())
+            .insert_resource(StageTransform::new(
+                self.config.stage_size,
+                self.config.window_size(),
+            ))
+            .insert_resource(ClearColor(Color::BLACK))
+            .insert_resource(Queue(VecDeque::new()))
+            .init_resource::<EntryFileData>()
+            .init_resource::<ProjectJsonTask>()
+            .init_resource::<ProjectImages>()
+            .init_resource::<LoadingProgress>()
+            .init_resource::<LoadError>()
+            .insert_resource(Ids::new())
+            .insert_resource(Mouse::default())
+            .init_resource::<Functions>()
+            .insert_resource(TickRate::new(self.config.max_ticks_per_frame))
+            .init_resource::<CallDepthLimit>()
+            .insert_resource(ExecutionBudget {
+                blocks_per_tick: self.config.blocks_per_tick,
+                on_exceeded: self.config.on_budget_exceeded,
+            })
+            .insert_resource(Debugger::new(&self.config.breakpoints))
+            .init_resource::<PreservedVariables>()
+            .init_resource::<FrameCapture>()
+            .insert_resource(
+                self.config
+                    .trace_path
+                    .as_deref()
+                    .map(TraceRecorder::create)
+                    .unwrap_or_default(),
+            )
+            .insert_resource(Random::new(self.config.seed))
+            .add_system_to_stage(CoreStage::PreUpdate, stage::stage_transform_system)
+            .add_startup_system(setup_camera)
+            .add_startup_system(stage::setup_stage)
+            .add_startup_system(debugger::setup_debugger)
+            .add_startup_system(capture::setup_capture)
+            .add_system(stage::fullscreen_system)
+            .add_system(capture::capture_system)
+            .add_system(stage::stage_system)
+            .add_state(AppState::Loading)
+            .add_system_set(
+                SystemSet::on_enter(AppState::Loading)
+                    .with_system(asset::setup_asset)
+                    .with_system(loading::setup_loading_ui),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Loading)
+                    .with_system(asset::check_asset_loading)
+                    .with_system(loading::loading_ui_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Loading).with_system(loading::despawn_ui::<LoadingUi>),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Error).with_system(loading::setup_error_ui),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Error).with_system(reload::hot_reload_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Error).with_system(loading::despawn_ui::<ErrorUi>),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::MainApp).with_system(setup))
+            .add_system_set(
+                SystemSet::on_exit(AppState::MainApp).with_system(reload::teardown_project),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::MainApp)
+                    .with_system(event::event_listener)
+                    .with_system(restart_project)
+                    .with_system(variable::variable_ui_system)
+                    .with_system(variable::slider_ui_system)
+                    .with_system(variable::variable_drag_system)
+                    .with_system(object::object_system)
+                    .with_system(text_box::text_box_system)
+                    .with_system(mouse::mouse_system)
+                    .with_system(debugger::debugger_input_system)
+                    .with_system(debugger::debugger_ui_system)
+                    .with_system(reload::hot_reload_system)
+                    .with_system(snapshot::snapshot_system),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::MainApp)
+                    .with_system(code::execute_code)
+                    .with_run_criteria(tick::tick_criteria),
+            )
+            .add_event::<Event>()
+            .add_event::<Restart>()
+            .add_asset::<EntryProject>()
+            .add_asset::<EntryFile>()
+            .init_asset_loader::<EntryAssetLoader>();
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_system_to_stage(RenderStage::Extract, capture::extract_capture_request)
+                .add_system_to_stage(RenderStage::Cleanup, capture::write_capture);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AppState {
+    Loading,
+    MainApp,
+    /// The project couldn't be loaded; see [`LoadError`].
+    Error,
+}
+
+#[derive(Default, Resource)]
+struct EntryFileData {
+    handle: Handle<EntryFile>,
+    project: Handle<EntryProject>,
+    /// The file the project is loaded from, after resolving directories.
+    path: String,
+}
+
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(stage::stage_camera());
+}
+
+#[allow(clippy::too_many_arguments)]
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<Event>,
+    project_assets: Res<Assets<EntryProject>>,
+    mut ids: ResMut<Ids>,
+    mut functions: ResMut<Functions>,
+    mut tick_rate: ResMut<TickRate>,
+    mut windows: ResMut<Windows>,
+    config: Res<GitteolConfig>,
+    entry_file_data: Res<EntryFileData>,
+    stage: Res<StageTransform>,
+    mut preserved: ResMut<PreservedVariables>,
+    project_images: Res<ProjectImages>,
+) {
+    let entry_project = project_assets.get(&entry_file_data.project).unwrap();
+    let project = &entry_project.project;
+
+    if let Some(window) = windows.get_primary_mut() {
+        window.set_title(project.name.to_string());
+    }
+
+    tick_rate.start(config.tick_rate.unwrap_or(project.speed));
+
+    *functions = Functions::new(&project.functions);
+
+    spawn_objects(
+        &mut commands,
+        &asset_server,
+        &config,
+        &project_images,
+        entry_project,
+        &mut ids,
+    );
+
+    let font = asset_server.load("fonts/NanumGothic.ttf");
+
+    let mut variable_ui_children = Vec::new();
+    for raw_variable in &project.variables {
+        let mut variable: Variable = raw_variable.clone().into();
+        if let Some(value) = preserved.0.remove(&variable.id) {
+            variable.value = value;
+        }
+        variable_ui_children.push(spawn_variable(
+            &mut commands,
+            font.clone(),
+            variable,
+            &stage,
+            &mut ids,
+        ))
+    }
+    preserved.0.clear();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(VariableUiRoot)
+        .push_children(&variable_ui_children);
+
+    events.send(Event {
+        event_type: EventType::WhenRunButtonClick,
+    });
+}
+
+fn restart_project(
+    mut restarts: EventReader<Restart>,
+    mut events: EventWriter<Event>,
+    project_assets: Res<Assets<EntryProject>>,
+    entry_file_data: Res<EntryFileData>,
+    mut objects: Query<(&Id, &mut Object)>,
+    mut variables: Query<&mut Variable>,
+) {
+    if restarts.iter().last().is_none() {
+        return;
+    }
+
+    let entry_project = project_assets.get(&entry_file_data.project).unwrap();
+    let project = &entry_project.project;
+
+    for (id, mut object) in &mut objects {
+        if let Some(raw_object) = project.objects.iter().find(|o| o.id.as_str() == id.0) {
+            *object = Object::new(raw_object, entry_project);
+        }
+    }
+    for mut variable in &mut variables {
+        if let Some(raw_variable) = project
+            .variables
+            .iter()
+            .find(|v| v.id.as_str() == variable.id.0)
+        {
+            *variable = raw_variable.clone().into();
+        }
+    }
+
+    events.send(Event {
+        event_type: EventType::WhenRunButtonClick,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asset::read_entry,
+        blocks::{BlockVec, Value},
+        code::{Code, Codes},
+    };
+
+    #[test]
+    fn restart_resets_the_project_and_runs_it_again() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<EntryProject>()
+            .add_event::<Event>()
+            .add_event::<Restart>()
+            .insert_resource(Queue(VecDeque::new()))
+            .add_system(restart_project)
+            .add_system(event::event_listener.after(restart_project));
+
+        let bytes = std::fs::read("assets/project.ent").unwrap();
+        let (project, _) = read_entry(&bytes).unwrap();
+        let raw_object = project.project.objects[0].clone();
+        let raw_variable = project.project.variables[0].clone();
+        let mut object = Object::new(&raw_object, &project);
+        object.translation.x += 100.0;
+        let mut variable: Variable = raw_variable.clone().into();
+        variable.value = Value::Number(42.0);
+
+        let handle = app
+            .world
+            .resource_mut::<Assets<EntryProject>>()
+            .add(project);
+        app.insert_resource(EntryFileData {
+            project: handle,
+            ..Default::default()
+        });
+        let code = Code {
+            id: Id::from_str("code"),
+            event: EventType::WhenRunButtonClick,
+            blocks: BlockVec::new(),
+        };
+        let object = app
+            .world
+            .spawn((Id::from(raw_object.id.clone()), object, Codes(vec![code])))
+            .id();
+        let variable = app.world.spawn(variable).id();
+
+        app.world.send_event(Restart);
+        app.update();
+
+        let object = app.world.get::<Object>(object).unwrap();
+        assert_eq!(object.translation.x, raw_object.entity.x);
+        let variable = app.world.get::<Variable>(variable).unwrap();
+        let expected: Value = raw_variable.value.into();
+        assert_eq!(variable.value.as_string(), expected.as_string());
+        assert_eq!(app.world.resource::<Queue>().0.len(), 1);
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Default)]
+pub(crate) struct LoadingProgress {
+    pub(crate) decoded: usize,
+    pub(crate) total: usize,
+}
+
+/// Why the project couldn't be loaded, shown in [`crate::AppState::Error`].
+#[derive(Resource, Default)]
+pub(crate) struct LoadError(pub(crate) String);
+
+#[derive(Component)]
+pub(crate) struct LoadingUi;
+
+#[derive(Component)]
+pub(crate) struct ErrorUi;
+
+fn spawn_message(commands: &mut Commands, font: Handle<Font>, message: String) -> Entity {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::BLACK.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    max_size: Size::new(Val::Percent(90.0), Val::Undefined),
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    message,
+                    TextStyle {
+                        font,
+                        font_size: 16.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..Default::default()
+            });
+        })
+        .id()
+}
+
+pub(crate) fn setup_loading_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/NanumGothic.ttf");
+    let ui = spawn_message(&mut commands, font, "Loading".to_string());
+    commands.entity(ui).insert(LoadingUi);
+}
+
+pub(crate) fn loading_ui_system(
+    progress: Res<LoadingProgress>,
+    uis: Query<&Children, With<LoadingUi>>,
+    mut texts: Query<&mut Text>,
+) {
+    for children in &uis {
+        if let Ok(mut text) = texts.get_mut(children[0]) {
+            text.sections[0].value = if progress.total == 0 {
+                "Loading".to_string()
+            } else {
+                format!("Loading pictures {}/{}", progress.decoded, progress.total)
+            };
+        }
+    }
+}
+
+pub(crate) fn setup_error_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    load_error: Res<LoadError>,
+) {
+    eprintln!("error: {}", load_error.0);
+    let font = asset_server.load("fonts/NanumGothic.ttf");
+    let ui = spawn_message(&mut commands, font, load_error.0.clone());
+    commands.entity(ui).insert(ErrorUi);
+}
+
+pub(crate) fn despawn_ui<T: Component>(mut commands: Commands, uis: Query<Entity, With<T>>) {
+    for ui in &uis {
+        commands.entity(ui).despawn_recursive();
+    }
+}
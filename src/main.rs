@@ -1,125 +1,211 @@
-use std::collections::VecDeque;
-
-use asset::{EntryAssetLoader, EntryFile, EntryProject};
-use bevy::{prelude::*, time::FixedTimestep};
-
-mod asset;
-mod blocks;
-mod code;
-mod common;
-mod event;
-mod mouse;
-mod object;
-mod variable;
-
-use code::Queue;
-use common::Ids;
-use event::{Event, EventType};
-use mouse::Mouse;
-use object::spawn_objects;
-use variable::spawn_variable;
-
-const PROJECT_FILE: &str = "project.ent";
-const WINDOW_ASPECT_RATIO: f32 = 480.0 / 270.0;
-const ENTRY_WIDTH: f32 = 480.0;
-const WINDOW_SIZE: f32 = 1.0;
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum AppState {
-    Loading,
-    MainApp,
+use bevy::{prelude::*, winit::WinitPlugin};
+use gitteol::{BudgetExceeded, GitteolConfig, GitteolPlugin, HeadlessPlugin};
+use std::str::FromStr;
+
+const USAGE: &str = "\
+usage: gitteol [options] [project]
+       gitteol replay <trace file> <tick>
+
+project is a .ent file, an unpacked project folder or a project.json
+in the assets folder (default: project.ent)
+
+options:
+  --seed <n>          seed of the random number blocks
+  --tick-rate <n>     ticks per second
+  --capture <ticks>   save every frame until <ticks> ticks have run, then exit
+  --watch             reload the project when its file changes
+  --keep-variables    keep variable values when reloading
+  --headless          run without a window
+  -h, --help          print this message
+";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    project: Option<String>,
+    seed: Option<u64>,
+    tick_rate: Option<u32>,
+    capture_ticks: Option<u64>,
+    watch: bool,
+    keep_variables: bool,
+    headless: bool,
 }
 
-#[derive(Default, Resource)]
-struct EntryFileData {
-    handle: Handle<EntryFile>,
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Options),
+    Replay { path: String, tick: u64 },
+    Help,
 }
 
-fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut events: EventWriter<Event>,
-    project_assets: Res<Assets<EntryProject>>,
-    mut ids: ResMut<Ids>,
-    mut windows: ResMut<Windows>,
-) {
-    commands.spawn(Camera2dBundle::default());
-
-    let project = &project_assets
-        .get(&asset_server.load(format!("{}#project", PROJECT_FILE)))
-        .unwrap()
-        .0;
-
-    let window = windows.primary_mut();
-    window.set_title(project.name.to_string());
-
-    spawn_objects(&mut commands, &asset_server, &project.objects, &mut ids);
-
-    let font = asset_server.load("fonts/NanumGothic.ttf");
-
-    let mut variable_ui_children = Vec::new();
-    for raw_variable in &project.variables {
-        variable_ui_children.push(spawn_variable(
-            &mut commands,
-            font.clone(),
-            raw_variable.clone().into(),
-            &mut ids,
-        ))
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    if args.peek().map(String::as_str) == Some("replay") {
+        args.next();
+        let path = args.next().ok_or("replay needs a trace file")?;
+        let tick = value("replay", args.next())?;
+        if let Some(arg) = args.next() {
+            return Err(format!("unexpected argument {}", arg));
+        }
+        return Ok(Command::Replay { path, tick });
     }
 
-    commands
-        .spawn(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .push_children(&variable_ui_children);
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--seed" => options.seed = Some(value(&arg, args.next())?),
+            "--tick-rate" => options.tick_rate = Some(value(&arg, args.next())?),
+            "--capture" => options.capture_ticks = Some(value(&arg, args.next())?),
+            "--watch" => options.watch = true,
+            "--keep-variables" => options.keep_variables = true,
+            "--headless" => options.headless = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown option {}", flag)),
+            _ if options.project.is_none() => options.project = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+    Ok(Command::Run(options))
+}
 
-    events.send(Event {
-        event_type: EventType::WhenRunButtonClick,
-    });
+fn value<T: FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", name, value))
 }
 
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::rgb(1.0, 1.0, 1.0)))
-        .insert_resource(Queue(VecDeque::new()))
-        .init_resource::<EntryFileData>()
-        .insert_resource(Ids::new())
-        .insert_resource(Mouse::default())
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
+    let mut config = GitteolConfig {
+        seed: std::env::var("GITTEOL_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok()),
+        trace_path: std::env::var_os("GITTEOL_TRACE").map(Into::into),
+        breakpoints: std::env::var("GITTEOL_BREAKPOINTS")
+            .map(|ids| {
+                ids.split(',')
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Replay { path, tick }) => {
+            match gitteol::replay_report(&path, tick) {
+                Ok(report) => print!("{}", report),
+                Err(err) => {
+                    eprintln!("cannot replay {}: {}", path, err);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+            return;
+        }
+        Err(err) => {
+            eprint!("{}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Some(project) = options.project {
+        config.project_path = project;
+    }
+    if options.seed.is_some() {
+        config.seed = options.seed;
+    }
+    config.preserve_variables = options.keep_variables;
+    config.capture_ticks = options.capture_ticks;
+    config.tick_rate = options.tick_rate;
+    let (watch, headless) = (options.watch, options.headless);
+    if let Some(blocks) = std::env::var("GITTEOL_BLOCKS_PER_TICK")
+        .ok()
+        .and_then(|val| val.parse().ok())
+    {
+        config.blocks_per_tick = blocks;
+    }
+    if std::env::var("GITTEOL_ON_BUDGET_EXCEEDED").as_deref() == Ok("abort") {
+        config.on_budget_exceeded = BudgetExceeded::Abort;
+    }
+
+    let window_size = config.window_size();
+    let plugins = DefaultPlugins.set(AssetPlugin {
+        watch_for_changes: watch,
+        ..Default::default()
+    });
+    let mut app = App::new();
+    if headless {
+        app.add_plugins(
+            plugins
+                .set(WindowPlugin {
+                    add_primary_window: false,
+                    exit_on_all_closed: false,
+                    ..Default::default()
+                })
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugin(HeadlessPlugin { window_size });
+    } else {
+        app.add_plugins(plugins.set(WindowPlugin {
             window: WindowDescriptor {
                 title: "깃털".to_string(),
-                width: ENTRY_WIDTH * WINDOW_SIZE,
-                height: ENTRY_WIDTH * WINDOW_SIZE / WINDOW_ASPECT_RATIO,
-                resizable: false,
+                width: window_size.x,
+                height: window_size.y,
+                resizable: true,
                 ..Default::default()
             },
             ..Default::default()
-        }))
-        .add_state(AppState::Loading)
-        .add_system_set(SystemSet::on_enter(AppState::Loading).with_system(asset::setup_asset))
-        .add_system_set(
-            SystemSet::on_update(AppState::Loading).with_system(asset::check_asset_loading),
-        )
-        .add_system_set(SystemSet::on_enter(AppState::MainApp).with_system(setup))
-        .add_system_set(
-            SystemSet::on_update(AppState::MainApp)
-                .with_system(event::event_listener)
-                .with_system(variable::variable_ui_system)
-                .with_system(object::object_system)
-                .with_system(mouse::mouse_system),
-        )
-        .add_system_set(
-            SystemSet::on_update(AppState::MainApp)
-                .with_system(code::execute_code)
-                .with_run_criteria(FixedTimestep::step(1.0 / 60.0)),
-        )
-        .add_event::<Event>()
-        .add_asset::<EntryProject>()
-        .add_asset::<EntryFile>()
-        .init_asset_loader::<EntryAssetLoader>()
-        .run();
+        }));
+    }
+    app.add_plugin(GitteolPlugin { config }).run();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn project_path_and_flags() {
+        let Ok(Command::Run(options)) = parse(&["--seed", "42", "game/project.json", "--watch"])
+        else {
+            panic!("expected a run command");
+        };
+        assert_eq!(options.project.as_deref(), Some("game/project.json"));
+        assert_eq!(options.seed, Some(42));
+        assert!(options.watch);
+        assert!(!options.headless);
+    }
+
+    #[test]
+    fn no_arguments_use_the_defaults() {
+        assert_eq!(parse(&[]), Ok(Command::Run(Options::default())));
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        assert!(parse(&["--wacth"]).is_err());
+        assert!(parse(&["a.ent", "b.ent"]).is_err());
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--tick-rate", "fast"]).is_err());
+    }
+
+    #[test]
+    fn replay_takes_a_trace_and_a_tick() {
+        assert_eq!(
+            parse(&["replay", "trace.jsonl", "120"]),
+            Ok(Command::Replay {
+                path: "trace.jsonl".to_string(),
+                tick: 120
+            })
+        );
+        assert!(parse(&["replay", "trace.jsonl"]).is_err());
+        assert!(parse(&["replay", "trace.jsonl", "120", "--watch"]).is_err());
+    }
 }
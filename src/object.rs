@@ -3,31 +3,127 @@ use std::str::FromStr;
 use bevy::prelude::*;
 
 use crate::{
-    blocks::parse_block,
+    asset::{EntryProject, ProjectImages},
+    blocks::Compiler,
     code::{Code, Codes},
     common::{Id, Ids},
     event::EventType,
-    PROJECT_FILE, WINDOW_SIZE,
+    stage::StageTransform,
+    text_box::{spawn_text_box, TextBox},
+    GitteolConfig,
 };
 
+#[derive(Clone)]
+pub(crate) struct Sound {
+    pub(crate) id: Id,
+    pub(crate) name: String,
+    pub(crate) duration: f32,
+}
+
 #[derive(Component)]
 pub(crate) struct Object {
     pub(crate) translation: Vec3,
     pub(crate) scale: Vec3,
+    /// Clockwise rotation of the picture in degrees.
+    pub(crate) rotation: f32,
+    /// Direction of movement in degrees, clockwise from up.
+    pub(crate) direction: f32,
+    pub(crate) sounds: Vec<Sound>,
+    pub(crate) text: Option<TextBox>,
+}
+
+impl Object {
+    pub(crate) fn new(value: &dotent::project::object::Object, project: &EntryProject) -> Self {
+        Object {
+            translation: Vec3::new(value.entity.x, value.entity.y, 0.0),
+            scale: Vec3::new(value.entity.scale_x, value.entity.scale_y, 1.0),
+            rotation: value.entity.rotation,
+            direction: value.entity.direction,
+            sounds: value
+                .sprite
+                .sounds
+                .iter()
+                .map(|sound| Sound {
+                    id: sound.id.clone().into(),
+                    name: sound.name.clone(),
+                    duration: sound.duration,
+                })
+                .collect(),
+            text: project.text_boxes.get(value.id.as_str()).cloned(),
+        }
+    }
 }
 
+const BACKGROUND_Z: f32 = -1.0;
+
 #[derive(Component)]
 pub(crate) enum ObjectType {
     Sprite,
+    /// The bottom-most sprite of a scene whose picture covers the stage, stretched to fit it
+    /// exactly.
+    Background,
+    TextBox,
+}
+impl ObjectType {
+    fn new(
+        object: &dotent::project::object::Object,
+        objects: &[dotent::project::object::Object],
+        stage_size: Vec2,
+    ) -> Self {
+        match object.object_type {
+            dotent::project::object::ObjectType::TextBox => ObjectType::TextBox,
+            dotent::project::object::ObjectType::Sprite
+                if is_background(object, objects, stage_size) =>
+            {
+                ObjectType::Background
+            }
+            dotent::project::object::ObjectType::Sprite => ObjectType::Sprite,
+        }
+    }
+}
+
+/// Index of the picture the object shows, or of its first picture if the selected one is
+/// missing. dotent doesn't export the picture type, so the caller looks it up.
+fn selected_picture(object: &dotent::project::object::Object) -> Option<usize> {
+    let pictures = &object.sprite.pictures;
+    let selected = pictures
+        .iter()
+        .position(|p| p.id.as_str() == object.selected_picture_id.as_str());
+    selected.or_else(|| (!pictures.is_empty()).then_some(0))
+}
+
+/// Entry has no background flag. Objects are listed top-most first, so a background is the last
+/// object of its scene, and its selected picture must cover the stage.
+fn is_background(
+    object: &dotent::project::object::Object,
+    objects: &[dotent::project::object::Object],
+    stage_size: Vec2,
+) -> bool {
+    let bottom_most = objects
+        .iter()
+        .rev()
+        .find(|o| o.scene.as_str() == object.scene.as_str())
+        .is_some_and(|o| o.id.as_str() == object.id.as_str());
+    let picture = selected_picture(object).map(|index| &object.sprite.pictures[index]);
+    let covers_stage = picture.is_some_and(|picture| {
+        let size = Vec2::new(
+            picture.dimension.width * object.entity.scale_x.abs(),
+            picture.dimension.height * object.entity.scale_y.abs(),
+        );
+        size.cmpge(stage_size - 1.0).all()
+    });
+    bottom_most && covers_stage
 }
 
 pub(crate) fn spawn_objects(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
-    objects: &Vec<dotent::project::object::Object>,
+    config: &GitteolConfig,
+    images: &ProjectImages,
+    project: &EntryProject,
     ids: &mut Ids,
 ) {
-    for object in objects {
+    for object in &project.project.objects {
         let script = &object.script;
         let mut codes = Vec::new();
         for code in &script.0 {
@@ -36,41 +132,202 @@ pub(crate) fn spawn_objects(
                 Err(_) => continue,
             };
 
-            let mut blocks = Vec::new();
-            for raw_block in code.iter().skip(1) {
-                blocks.append(&mut parse_block(raw_block));
-            }
+            let blocks = Compiler::compile(&code[1..]);
 
-            codes.push(Code { event, blocks });
+            codes.push(Code {
+                id: code[0].id.clone().into(),
+                event,
+                blocks,
+            });
         }
 
-        let texture = match &object.sprite.pictures[0].filename {
-            Some(f) => format!("{}#{}", PROJECT_FILE, f),
-            None => "entrybot1.png".to_string(),
+        let object_type = ObjectType::new(object, &project.project.objects, config.stage_size);
+        let object_data = Object::new(object, project);
+
+        let entity = match (&object_type, &object_data.text) {
+            (ObjectType::TextBox, Some(text_box)) => {
+                spawn_text_box(commands, asset_server, text_box)
+            }
+            _ => {
+                let texture = selected_picture(object)
+                    .map(|index| &object.sprite.pictures[index])
+                    .and_then(|p| images.0.get(p.filename.as_ref()?))
+                    .cloned()
+                    .unwrap_or_else(|| asset_server.load("entrybot1.png"));
+                let custom_size = match object_type {
+                    ObjectType::Background => Some(config.stage_size),
+                    _ => None,
+                };
+                commands
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size,
+                            ..Default::default()
+                        },
+                        texture,
+                        ..Default::default()
+                    })
+                    .id()
+            }
         };
 
         let id: Id = object.id.clone().into();
-        let entity = commands
-            .spawn(SpriteBundle {
-                texture: asset_server.load(texture),
-                ..Default::default()
-            })
-            .insert(Object {
-                translation: Vec3::new(object.entity.x, object.entity.y, 0.0),
-                scale: Vec3::new(object.entity.scale_x, object.entity.scale_y, 1.0),
-            })
+        commands
+            .entity(entity)
+            .insert(object_data)
             .insert(id.clone())
-            .insert(ObjectType::Sprite)
-            .insert(Codes(codes))
-            .id();
+            .insert(object_type)
+            .insert(Codes(codes));
 
         ids.insert(id, entity);
     }
 }
 
-pub(crate) fn object_system(mut objects: Query<(&Object, &mut Transform)>) {
-    for (object, mut transform) in &mut objects {
-        transform.translation = object.translation * WINDOW_SIZE;
-        transform.scale = object.scale * WINDOW_SIZE;
+pub(crate) fn object_system(
+    stage: Res<StageTransform>,
+    mut objects: Query<(&Object, &ObjectType, &mut Transform)>,
+) {
+    for (object, object_type, mut transform) in &mut objects {
+        let translation = stage.stage_to_world(object.translation.truncate());
+        match object_type {
+            ObjectType::Background => {
+                transform.translation = translation.extend(BACKGROUND_Z);
+                transform.scale = Vec3::splat(stage.scale);
+            }
+            _ => {
+                transform.translation = translation.extend(object.translation.z);
+                transform.scale = object.scale * stage.scale;
+                transform.rotation = Quat::from_rotation_z(-object.rotation.to_radians());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const STAGE_SIZE: Vec2 = Vec2::new(480.0, 270.0);
+
+    fn object(
+        id: &str,
+        object_type: &str,
+        scene: &str,
+        picture_size: (f32, f32),
+        scale: f32,
+    ) -> dotent::project::object::Object {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": id,
+            "script": "[]",
+            "objectType": object_type,
+            "rotateMethod": "free",
+            "scene": scene,
+            "sprite": {
+                "pictures": [{
+                    "id": format!("{id}_picture"),
+                    "dimension": { "width": picture_size.0, "height": picture_size.1 },
+                    "fileurl": "",
+                    "filename": null,
+                    "name": "picture",
+                    "imageType": "png"
+                }],
+                "sounds": []
+            },
+            "selectedPictureId": format!("{id}_picture"),
+            "lock": false,
+            "entity": {
+                "x": 0, "y": 0, "regX": 0, "regY": 0, "scaleX": scale, "scaleY": scale,
+                "rotation": 0, "direction": 90, "width": picture_size.0,
+                "height": picture_size.1, "font": "", "visible": true
+            }
+        }))
+        .unwrap()
+    }
+
+    fn is_background_object(objects: &[dotent::project::object::Object], index: usize) -> bool {
+        matches!(
+            ObjectType::new(&objects[index], objects, STAGE_SIZE),
+            ObjectType::Background
+        )
+    }
+
+    #[test]
+    fn bottom_most_full_stage_sprite_is_the_background() {
+        let objects = [
+            object("player", "sprite", "s1", (100.0, 100.0), 1.0),
+            object("background", "sprite", "s1", (960.0, 540.0), 0.5),
+        ];
+        assert!(!is_background_object(&objects, 0));
+        assert!(is_background_object(&objects, 1));
+    }
+
+    #[test]
+    fn full_stage_sprite_above_others_is_a_sprite() {
+        let objects = [
+            object("overlay", "sprite", "s1", (480.0, 270.0), 1.0),
+            object("player", "sprite", "s1", (100.0, 100.0), 1.0),
+        ];
+        assert!(!is_background_object(&objects, 0));
+        assert!(!is_background_object(&objects, 1));
+    }
+
+    #[test]
+    fn each_scene_has_its_own_background() {
+        let objects = [
+            object("first", "sprite", "s1", (480.0, 270.0), 1.0),
+            object("second", "sprite", "s2", (480.0, 270.0), 1.0),
+        ];
+        assert!(is_background_object(&objects, 0));
+        assert!(is_background_object(&objects, 1));
+    }
+
+    #[test]
+    fn text_boxes_are_never_backgrounds() {
+        let objects = [object("text", "textbox", "s1", (480.0, 270.0), 1.0)];
+        assert!(matches!(
+            ObjectType::new(&objects[0], &objects, STAGE_SIZE),
+            ObjectType::TextBox
+        ));
+    }
+
+    /// Gives the object a small first picture, so that only its selected one covers the stage.
+    fn with_small_first_picture(
+        object: dotent::project::object::Object,
+        selected: &str,
+    ) -> dotent::project::object::Object {
+        let mut json = serde_json::to_value(object).unwrap();
+        let mut small = json["sprite"]["pictures"][0].clone();
+        small["id"] = json!("small");
+        small["dimension"] = json!({ "width": 10, "height": 10 });
+        json["sprite"]["pictures"]
+            .as_array_mut()
+            .unwrap()
+            .insert(0, small);
+        json["selectedPictureId"] = json!(selected);
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn the_selected_picture_is_used() {
+        let background = object("background", "sprite", "s1", (480.0, 270.0), 1.0);
+        let objects = [with_small_first_picture(background, "background_picture")];
+        assert_eq!(selected_picture(&objects[0]), Some(1));
+        assert!(is_background_object(&objects, 0));
+
+        let small = object("small", "sprite", "s1", (480.0, 270.0), 1.0);
+        let objects = [with_small_first_picture(small, "small")];
+        assert_eq!(selected_picture(&objects[0]), Some(0));
+        assert!(!is_background_object(&objects, 0));
+    }
+
+    #[test]
+    fn missing_selection_falls_back_to_the_first_picture() {
+        let background = object("background", "sprite", "s1", (480.0, 270.0), 1.0);
+        let objects = [with_small_first_picture(background, "deleted")];
+        assert_eq!(selected_picture(&objects[0]), Some(0));
+        assert!(!is_background_object(&objects, 0));
     }
 }
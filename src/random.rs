@@ -0,0 +1,46 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+
+/// SplitMix64 generator, small enough to keep runs reproducible from a single seed.
+#[derive(Resource)]
+pub(crate) struct Random(u64);
+
+impl Random {
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+        Random(seed)
+    }
+
+    /// Returns a random number in `0.0..1.0`, like JavaScript's `Math.random()`.
+    pub(crate) fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(seed: u64) -> Vec<f64> {
+        let mut random = Random::new(Some(seed));
+        (0..10).map(|_| random.next()).collect()
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        assert_eq!(sequence(42), sequence(42));
+        assert_ne!(sequence(42), sequence(43));
+        assert!(sequence(7).iter().all(|value| (0.0..1.0).contains(value)));
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::{
+    asset::EntryFile,
+    blocks::Value,
+    code::Queue,
+    common::{Id, Ids},
+    object::Object,
+    variable::{Variable, VariableUiRoot},
+    AppState, EntryFileData, GitteolConfig,
+};
+
+/// Variable values carried over a reload when [`GitteolConfig::preserve_variables`] is set.
+#[derive(Resource, Default)]
+pub(crate) struct PreservedVariables(pub(crate) HashMap<Id, Value>);
+
+/// Goes back to loading when the `.ent` file changes on disk, which re-runs `setup`.
+///
+/// Bevy only reports changes when the asset server watches for them.
+pub(crate) fn hot_reload_system(
+    mut asset_events: EventReader<AssetEvent<EntryFile>>,
+    entry_file_data: Res<EntryFileData>,
+    mut state: ResMut<State<AppState>>,
+) {
+    let modified = asset_events.iter().any(|event| {
+        matches!(event, AssetEvent::Modified { handle } if *handle == entry_file_data.handle)
+    });
+    if modified {
+        info!("project changed, reloading");
+        state.set(AppState::Loading).unwrap();
+    }
+}
+
+/// Removes everything `setup` spawned for the project and stops its code.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn teardown_project(
+    mut commands: Commands,
+    config: Res<GitteolConfig>,
+    objects: Query<Entity, With<Object>>,
+    variables: Query<(Entity, &Variable)>,
+    variable_uis: Query<Entity, With<VariableUiRoot>>,
+    mut ids: ResMut<Ids>,
+    mut queue: ResMut<Queue>,
+    mut preserved: ResMut<PreservedVariables>,
+) {
+    for entity in objects.iter().chain(variable_uis.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+    for (entity, variable) in &variables {
+        if config.preserve_variables {
+            preserved
+                .0
+                .insert(variable.id.clone(), variable.value.clone());
+        }
+        commands.entity(entity).despawn();
+    }
+    ids.clear();
+    queue.0.clear();
+}
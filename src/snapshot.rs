@@ -0,0 +1,333 @@
+use std::{
+    io::{Read, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde_json::json;
+use tar::{Archive, Builder, Header};
+
+use crate::{
+    asset::{image_archive_path, EntryFile, EntryProject},
+    blocks::Value,
+    common::Id,
+    object::Object,
+    variable::Variable,
+    EntryFileData, GitteolConfig,
+};
+
+const PROJECT_JSON: &str = "temp/project.json";
+
+/// Writes the running state into a copy of `project.json`.
+///
+/// Only what the player changes at runtime is written back: object positions, scale and text,
+/// and variable values. Everything else is kept as it was read.
+pub(crate) fn snapshot_json<'a>(
+    json: &serde_json::Value,
+    objects: impl IntoIterator<Item = (&'a Id, &'a Object)>,
+    variables: impl IntoIterator<Item = &'a Variable>,
+) -> serde_json::Value {
+    let mut json = json.clone();
+
+    for (id, object) in objects {
+        let Some(raw) = find_by_id(&mut json["objects"], &id.0) else {
+            continue;
+        };
+        let entity = &mut raw["entity"];
+        entity["x"] = json!(object.translation.x);
+        entity["y"] = json!(object.translation.y);
+        entity["scaleX"] = json!(object.scale.x);
+        entity["scaleY"] = json!(object.scale.y);
+        entity["rotation"] = json!(object.rotation);
+        entity["direction"] = json!(object.direction);
+        if let Some(text_box) = &object.text {
+            entity["text"] = json!(text_box.text);
+            entity["colour"] = json!(text_box.colour);
+            entity["bgColor"] = json!(text_box.bg_color);
+        }
+    }
+
+    for variable in variables {
+        let Some(raw) = find_by_id(&mut json["variables"], &variable.id.0) else {
+            continue;
+        };
+        let Some(value) = variable_json(&variable.value, &raw["value"]) else {
+            continue;
+        };
+        raw["value"] = value;
+        raw["visible"] = json!(variable.visible);
+        let pos = variable.pos.as_vec2();
+        raw["x"] = json!(pos.x);
+        raw["y"] = json!(pos.y);
+    }
+
+    json
+}
+
+/// Writes `value` as the JSON type the variable was read as, so that the snapshot reads back the
+/// same way. A value which cannot be written as a number becomes a string.
+fn variable_json(value: &Value, original: &serde_json::Value) -> Option<serde_json::Value> {
+    if let Value::Memory(_) = value {
+        return None;
+    }
+    let number = match value {
+        Value::String(_) if original.is_number() => value.as_number().ok(),
+        Value::Number(_) | Value::Bool(_) if !original.is_string() => value.as_number().ok(),
+        _ => None,
+    };
+    Some(match number {
+        Some(number) => json!(number),
+        None => json!(value.as_string().ok()?),
+    })
+}
+
+fn find_by_id<'a>(list: &'a mut serde_json::Value, id: &str) -> Option<&'a mut serde_json::Value> {
+    list.as_array_mut()?
+        .iter_mut()
+        .find(|item| item["id"] == id)
+}
+
+fn append(
+    builder: &mut Builder<GzEncoder<Vec<u8>>>,
+    path: &str,
+    data: &[u8],
+) -> anyhow::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+/// Packs `project.json` with the pictures of `entry_file` into a `.ent` archive.
+///
+/// An archive read from a `.ent` keeps all of its files; pictures read next to a `project.json`
+/// are put where Entry keeps them, under `temp/`.
+pub(crate) fn write_entry(project_json: &[u8], entry_file: &EntryFile) -> anyhow::Result<Vec<u8>> {
+    let mut builder = Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+    match &entry_file.archive {
+        Some(archive) => {
+            let mut archive = Archive::new(GzDecoder::new(&archive[..]));
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let path = entry.path()?.to_string_lossy().into_owned();
+                if path == PROJECT_JSON {
+                    continue;
+                }
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data)?;
+                append(&mut builder, &path, &data)?;
+            }
+        }
+        None => {
+            for image in &entry_file.images {
+                let path = image_archive_path(&image.name, &image.ext);
+                append(&mut builder, &path, &image.data)?;
+            }
+        }
+    }
+    append(&mut builder, PROJECT_JSON, project_json)?;
+
+    let mut encoder = builder.into_inner()?;
+    encoder.flush()?;
+    Ok(encoder.finish()?)
+}
+
+fn save_requested(keys: &Input<KeyCode>) -> bool {
+    let ctrl = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    ctrl && keys.just_pressed(KeyCode::S)
+}
+
+/// Ctrl+S saves the running project, e.g. a paused game, as a new `.ent` file.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn snapshot_system(
+    keys: Res<Input<KeyCode>>,
+    config: Res<GitteolConfig>,
+    entry_file_data: Res<EntryFileData>,
+    entry_files: Res<Assets<EntryFile>>,
+    projects: Res<Assets<EntryProject>>,
+    objects: Query<(&Id, &Object)>,
+    variables: Query<&Variable>,
+) {
+    if !save_requested(&keys) {
+        return;
+    }
+    let (Some(entry_file), Some(project)) = (
+        entry_files.get(&entry_file_data.handle),
+        projects.get(&entry_file_data.project),
+    ) else {
+        return;
+    };
+
+    let json = snapshot_json(&project.json, &objects, &variables);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = config
+        .snapshot_dir
+        .join(format!("snapshot-{}.ent", timestamp));
+
+    let result = serde_json::to_vec(&json)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| write_entry(&json, entry_file))
+        .and_then(|data| Ok(std::fs::write(&path, data)?));
+    match result {
+        Ok(()) => info!("saved snapshot to {}", path.display()),
+        Err(err) => error!("cannot save snapshot to {}: {:#}", path.display(), err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asset::read_entry, asset::ImageData, common::LocalPos};
+
+    #[test]
+    fn snapshot_round_trips_through_read_entry() {
+        let bytes = std::fs::read("assets/project.ent").unwrap();
+        let (project, images) = read_entry(&bytes).unwrap();
+        let raw = &project.project.objects[0];
+        let id: Id = raw.id.clone().into();
+        let mut object = Object::new(raw, &project);
+        object.translation.x += 42.0;
+        object.scale.y = 2.5;
+
+        let json = snapshot_json(&project.json, [(&id, &object)], []);
+        let entry_file = EntryFile {
+            images,
+            archive: Some(bytes),
+            error: None,
+        };
+        let data = write_entry(&serde_json::to_vec(&json).unwrap(), &entry_file).unwrap();
+
+        let (saved, saved_images) = read_entry(&data).unwrap();
+        let saved_raw = &saved.project.objects[0];
+        assert_eq!(saved_raw.entity.x, raw.entity.x + 42.0);
+        assert_eq!(saved_raw.entity.scale_y, 2.5);
+        assert_eq!(saved_raw.entity.y, raw.entity.y);
+        assert_eq!(saved.project.objects.len(), project.project.objects.len());
+        assert_eq!(saved_images.len(), entry_file.images.len());
+    }
+
+    fn variable(id: &str, value: Value) -> Variable {
+        Variable {
+            id: Id::from_str(id),
+            variable_type: dotent::project::variable::VariableType::Variable,
+            name: id.to_string(),
+            value,
+            visible: true,
+            pos: LocalPos::new(0.0, 0.0),
+            min_value: 0.0,
+            max_value: 100.0,
+        }
+    }
+
+    #[test]
+    fn variables_keep_their_json_type() {
+        let json = json!({
+            "objects": [],
+            "variables": [
+                { "id": "text", "value": "0" },
+                { "id": "number", "value": 0 },
+                { "id": "flag", "value": 0 },
+                { "id": "word", "value": 0 },
+            ],
+        });
+        let variables = [
+            variable("text", Value::Bool(true)),
+            variable("number", Value::String("3.5".to_string())),
+            variable("flag", Value::Bool(true)),
+            variable("word", Value::String("엔트리".to_string())),
+        ];
+
+        let json = snapshot_json(&json, [], &variables);
+        let values: Vec<_> = json["variables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|raw| raw["value"].clone())
+            .collect();
+        assert_eq!(
+            values,
+            [json!("true"), json!(3.5), json!(1.0), json!("엔트리")]
+        );
+    }
+
+    #[test]
+    fn project_json_snapshots_load_their_pictures_back() {
+        let bytes = std::fs::read("assets/project.ent").unwrap();
+        let (project, _) = read_entry(&bytes).unwrap();
+        let entry_file = EntryFile {
+            images: vec![
+                ImageData {
+                    name: "0hf5cuqy".to_string(),
+                    ext: "png".to_string(),
+                    data: b"picture".to_vec(),
+                },
+                ImageData {
+                    name: "a".to_string(),
+                    ext: "svg".to_string(),
+                    data: b"short".to_vec(),
+                },
+            ],
+            archive: None,
+            error: None,
+        };
+        let data = write_entry(&serde_json::to_vec(&project.json).unwrap(), &entry_file).unwrap();
+
+        let mut paths = Vec::new();
+        let mut archive = Archive::new(GzDecoder::new(&data[..]));
+        for entry in archive.entries().unwrap() {
+            paths.push(
+                entry
+                    .unwrap()
+                    .path()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+        }
+        assert_eq!(
+            paths,
+            [
+                "temp/0h/f5/image/0hf5cuqy.png",
+                "temp/a_/__/image/a.svg",
+                PROJECT_JSON
+            ]
+        );
+
+        let (saved, saved_images) = read_entry(&data).unwrap();
+        assert_eq!(saved.project.name, project.project.name);
+        let saved_images: Vec<_> = saved_images
+            .iter()
+            .map(|image| (image.name.as_str(), image.ext.as_str(), &image.data[..]))
+            .collect();
+        assert_eq!(
+            saved_images,
+            [
+                ("0hf5cuqy", "png", &b"picture"[..]),
+                ("a", "svg", &b"short"[..])
+            ]
+        );
+    }
+
+    #[test]
+    fn ctrl_s_requests_a_save_once() {
+        let mut keys = Input::<KeyCode>::default();
+        keys.press(KeyCode::S);
+        assert!(!save_requested(&keys));
+
+        keys.release(KeyCode::S);
+        keys.clear();
+        keys.press(KeyCode::RControl);
+        keys.press(KeyCode::S);
+        assert!(save_requested(&keys));
+
+        keys.clear();
+        assert!(!save_requested(&keys));
+    }
+}
@@ -0,0 +1,175 @@
+use bevy::{prelude::*, window::WindowMode};
+
+use crate::GitteolConfig;
+
+const LETTERBOX_Z: f32 = 900.0;
+const BACKDROP_Z: f32 = -900.0;
+const BACKDROP_COLOR: Color = Color::WHITE;
+
+/// Maps Entry's stage coordinates onto the window, keeping the stage's aspect ratio.
+///
+/// The stage is centered in the window and scaled to fit; the remaining area is letterboxed.
+#[derive(Resource, Clone, Copy, Debug)]
+pub(crate) struct StageTransform {
+    pub(crate) scale: f32,
+    stage_size: Vec2,
+    window_size: Vec2,
+}
+impl StageTransform {
+    pub(crate) fn new(stage_size: Vec2, window_size: Vec2) -> Self {
+        let scale = (window_size / stage_size).min_element();
+        StageTransform {
+            scale,
+            stage_size,
+            window_size,
+        }
+    }
+
+    /// Stage position (y up, origin at the center) to world position.
+    pub(crate) fn stage_to_world(&self, pos: Vec2) -> Vec2 {
+        pos * self.scale
+    }
+
+    /// Window cursor position (y up, origin at the bottom left) to stage position.
+    pub(crate) fn cursor_to_stage(&self, cursor: Vec2) -> Vec2 {
+        (cursor - self.window_size / 2.0) / self.scale
+    }
+
+    /// Stage position (y down, origin at the center) to UI position from the window's top left.
+    pub(crate) fn stage_to_ui(&self, pos: Vec2) -> Vec2 {
+        self.window_size / 2.0 + pos * self.scale
+    }
+
+    /// Size of the window the stage is laid out in.
+    pub(crate) fn window_size(&self) -> Vec2 {
+        self.window_size
+    }
+
+    /// Size of the stage on screen.
+    pub(crate) fn screen_size(&self) -> Vec2 {
+        self.stage_size * self.scale
+    }
+}
+
+pub(crate) fn stage_transform_system(
+    windows: Res<Windows>,
+    config: Res<GitteolConfig>,
+    mut stage: ResMut<StageTransform>,
+) {
+    let Some(window) = windows.get_primary() else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    if window_size.min_element() > 0.0 {
+        *stage = StageTransform::new(config.stage_size, window_size);
+    }
+}
+
+pub(crate) fn fullscreen_system(keys: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+    let alt = keys.any_pressed([KeyCode::LAlt, KeyCode::RAlt]);
+    if !(alt && keys.just_pressed(KeyCode::Return)) {
+        return;
+    }
+    let Some(window) = windows.get_primary_mut() else {
+        return;
+    };
+    window.set_mode(match window.mode() {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+        _ => WindowMode::Windowed,
+    });
+}
+
+/// A 2D camera which also sees the backdrop and backgrounds below z = 0.
+pub(crate) fn stage_camera() -> Camera2dBundle {
+    let mut camera = Camera2dBundle::new_with_far(2000.0);
+    camera.transform.translation.z = 1000.0;
+    camera
+}
+
+/// One of the four bars covering the window outside the stage.
+#[derive(Component)]
+pub(crate) struct Letterbox(Vec2);
+
+/// The empty white stage, drawn behind every object.
+#[derive(Component)]
+pub(crate) struct Backdrop;
+
+pub(crate) fn setup_stage(mut commands: Commands, config: Res<GitteolConfig>) {
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: BACKDROP_COLOR,
+                custom_size: Some(config.stage_size),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, BACKDROP_Z),
+            ..Default::default()
+        })
+        .insert(Backdrop);
+    for side in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+        commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::BLACK,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(Letterbox(side));
+    }
+}
+
+pub(crate) fn stage_system(
+    stage: Res<StageTransform>,
+    mut backdrops: Query<&mut Transform, (With<Backdrop>, Without<Letterbox>)>,
+    mut bars: Query<(&Letterbox, &mut Sprite, &mut Transform)>,
+) {
+    for mut transform in &mut backdrops {
+        transform.scale = Vec3::splat(stage.scale);
+    }
+
+    let screen_size = stage.screen_size();
+    let bar_size = stage
+        .window_size
+        .max_element()
+        .max(screen_size.max_element())
+        * 2.0;
+    for (Letterbox(side), mut sprite, mut transform) in &mut bars {
+        sprite.custom_size = Some(Vec2::splat(bar_size));
+        let offset = *side * (screen_size + bar_size) / 2.0;
+        transform.translation = offset.extend(LETTERBOX_Z);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAGE: Vec2 = Vec2::new(480.0, 270.0);
+
+    #[test]
+    fn wide_window_is_pillarboxed() {
+        let stage = StageTransform::new(STAGE, Vec2::new(1920.0, 540.0));
+        assert_eq!(stage.scale, 2.0);
+        assert_eq!(stage.screen_size(), Vec2::new(960.0, 540.0));
+        assert_eq!(
+            stage.stage_to_world(Vec2::new(240.0, -135.0)),
+            Vec2::new(480.0, -270.0)
+        );
+    }
+
+    #[test]
+    fn cursor_and_ui_use_the_same_mapping() {
+        let stage = StageTransform::new(STAGE, Vec2::new(480.0, 540.0));
+        assert_eq!(stage.scale, 1.0);
+        assert_eq!(stage.cursor_to_stage(Vec2::new(240.0, 270.0)), Vec2::ZERO);
+        assert_eq!(
+            stage.cursor_to_stage(Vec2::new(0.0, 405.0)),
+            Vec2::new(-240.0, 135.0)
+        );
+        assert_eq!(
+            stage.stage_to_ui(Vec2::new(-240.0, -135.0)),
+            Vec2::new(0.0, 135.0)
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use bevy::{
+    prelude::*,
+    text::{Text2dBounds, Text2dSize},
+};
+use serde::Deserialize;
+
+use crate::object::Object;
+
+const DEFAULT_FONT_SIZE: f32 = 20.0;
+const BACKGROUND_Z: f32 = -0.1;
+const LINE_Z: f32 = 0.1;
+
+/// The text of a `textBox` object, read from the entity fields dotent doesn't know about.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TextBox {
+    #[serde(default)]
+    pub(crate) text: String,
+    #[serde(default = "default_colour")]
+    pub(crate) colour: String,
+    #[serde(default = "default_bg_color")]
+    pub(crate) bg_color: String,
+    /// CSS font shorthand such as `bold 20px NanumGothic`.
+    #[serde(default)]
+    pub(crate) font: String,
+    #[serde(default)]
+    pub(crate) line_break: bool,
+    #[serde(default)]
+    pub(crate) under_line: bool,
+    #[serde(default)]
+    pub(crate) strike: bool,
+    #[serde(default)]
+    pub(crate) width: f32,
+    #[serde(default)]
+    pub(crate) height: f32,
+}
+
+fn default_colour() -> String {
+    "#000000".to_string()
+}
+
+fn default_bg_color() -> String {
+    "transparent".to_string()
+}
+
+impl TextBox {
+    pub(crate) fn font_size(&self) -> f32 {
+        self.font
+            .split_whitespace()
+            .find_map(|token| token.strip_suffix("px")?.parse().ok())
+            .unwrap_or(DEFAULT_FONT_SIZE)
+    }
+
+    pub(crate) fn is_bold(&self) -> bool {
+        self.font.split_whitespace().any(|token| token == "bold")
+    }
+}
+
+/// Parses Entry's `#rrggbb` colors; `transparent` and unknown values are `None`.
+pub(crate) fn parse_color(color: &str) -> Option<Color> {
+    Color::hex(color.strip_prefix('#')?).ok()
+}
+
+#[derive(Component, Clone, Copy)]
+pub(crate) enum TextBoxPart {
+    Background,
+    UnderLine,
+    Strike,
+}
+
+pub(crate) fn spawn_text_box(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    text_box: &TextBox,
+) -> Entity {
+    let font = asset_server.load(if text_box.is_bold() {
+        "fonts/NanumGothicBold.ttf"
+    } else {
+        "fonts/NanumGothic.ttf"
+    });
+
+    commands
+        .spawn(Text2dBundle {
+            text: Text::from_section(
+                text_box.text.clone(),
+                TextStyle {
+                    font,
+                    font_size: text_box.font_size(),
+                    color: parse_color(&text_box.colour).unwrap_or(Color::BLACK),
+                },
+            )
+            .with_alignment(TextAlignment::CENTER),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (part, z) in [
+                (TextBoxPart::Background, BACKGROUND_Z),
+                (TextBoxPart::UnderLine, LINE_Z),
+                (TextBoxPart::Strike, LINE_Z),
+            ] {
+                parent
+                    .spawn(SpriteBundle {
+                        transform: Transform::from_xyz(0.0, 0.0, z),
+                        ..Default::default()
+                    })
+                    .insert(part);
+            }
+        })
+        .id()
+}
+
+pub(crate) fn text_box_system(
+    mut text_boxes: Query<(
+        &Object,
+        &mut Text,
+        &mut Text2dBounds,
+        &Text2dSize,
+        &Children,
+    )>,
+    mut parts: Query<(&TextBoxPart, &mut Sprite, &mut Transform, &mut Visibility)>,
+) {
+    for (object, mut text, mut bounds, size, children) in &mut text_boxes {
+        let Some(text_box) = &object.text else {
+            continue;
+        };
+
+        let section = &mut text.sections[0];
+        section.value.clone_from(&text_box.text);
+        section.style.font_size = text_box.font_size();
+        section.style.color = parse_color(&text_box.colour).unwrap_or(Color::BLACK);
+
+        bounds.size = if text_box.line_break {
+            Vec2::new(text_box.width, f32::MAX)
+        } else {
+            Vec2::splat(f32::MAX)
+        };
+
+        let box_size = if text_box.line_break {
+            Vec2::new(text_box.width, text_box.height.max(size.size.y))
+        } else {
+            size.size
+        };
+        let thickness = (text_box.font_size() / 15.0).max(1.0);
+
+        for child in children {
+            let Ok((part, mut sprite, mut transform, mut visibility)) = parts.get_mut(*child)
+            else {
+                continue;
+            };
+            match part {
+                TextBoxPart::Background => {
+                    let color = parse_color(&text_box.bg_color);
+                    visibility.is_visible = color.is_some();
+                    sprite.color = color.unwrap_or(Color::NONE);
+                    sprite.custom_size = Some(box_size);
+                }
+                TextBoxPart::UnderLine | TextBoxPart::Strike => {
+                    let enabled = match part {
+                        TextBoxPart::UnderLine => text_box.under_line,
+                        _ => text_box.strike,
+                    };
+                    visibility.is_visible = enabled;
+                    sprite.color = section.style.color;
+                    sprite.custom_size = Some(Vec2::new(size.size.x, thickness));
+                    transform.translation.y = match part {
+                        TextBoxPart::UnderLine => -size.size.y / 2.0,
+                        _ => 0.0,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_use_entry_defaults() {
+        let text_box: TextBox = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(text_box.text, "");
+        assert_eq!(text_box.colour, "#000000");
+        assert_eq!(text_box.bg_color, "transparent");
+        assert_eq!(text_box.font_size(), DEFAULT_FONT_SIZE);
+        assert!(!text_box.is_bold());
+    }
+
+    #[test]
+    fn parses_hex_colors_only() {
+        assert_eq!(parse_color("#ff0000"), Some(Color::rgb(1.0, 0.0, 0.0)));
+        assert_eq!(parse_color("transparent"), None);
+        assert_eq!(parse_color("red"), None);
+        assert_eq!(parse_color("#zzzzzz"), None);
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::{ecs::schedule::ShouldRun, prelude::*};
+
+/// Ticks which may run in one frame to catch up after frames drop.
+pub(crate) const DEFAULT_MAX_TICKS_PER_FRAME: u32 = 5;
+
+/// How often code runs, from the project's `speed` (Entry's FPS).
+///
+/// Every tick stands for `step` seconds of project time, however long the frame took. When frames
+/// drop, up to `max_ticks_per_frame` ticks catch up in one frame and the rest are skipped, so a
+/// slow machine runs the project slower instead of stalling on a growing backlog.
+#[derive(Resource)]
+pub(crate) struct TickRate {
+    step: f64,
+    max_ticks_per_frame: u32,
+    accumulator: f64,
+    looping: bool,
+    frame_ticks: u32,
+    ticks: u64,
+}
+impl Default for TickRate {
+    fn default() -> Self {
+        TickRate::new(DEFAULT_MAX_TICKS_PER_FRAME)
+    }
+}
+
+impl TickRate {
+    pub(crate) fn new(max_ticks_per_frame: u32) -> Self {
+        TickRate {
+            step: 1.0 / 60.0,
+            max_ticks_per_frame: max_ticks_per_frame.max(1),
+            accumulator: 0.0,
+            looping: false,
+            frame_ticks: 0,
+            ticks: 0,
+        }
+    }
+
+    /// Starts counting ticks again for a project running at `speed` ticks per second.
+    pub(crate) fn start(&mut self, speed: u32) {
+        self.step = 1.0 / speed.max(1) as f64;
+        self.accumulator = 0.0;
+        self.ticks = 0;
+    }
+
+    /// Seconds of project time per tick.
+    pub(crate) fn step(&self) -> f32 {
+        self.step as f32
+    }
+
+    /// Ticks run since the project started.
+    pub(crate) fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Whether another tick runs in a frame which took `delta` seconds.
+    ///
+    /// Called once at the start of each frame, then again after every tick it allowed.
+    fn next_tick(&mut self, delta: f64) -> bool {
+        if !self.looping {
+            self.accumulator += delta;
+            self.frame_ticks = 0;
+        }
+
+        if self.accumulator >= self.step && self.frame_ticks < self.max_ticks_per_frame {
+            self.accumulator -= self.step;
+            self.frame_ticks += 1;
+            self.ticks += 1;
+            self.looping = true;
+            true
+        } else {
+            // Skip what can't be caught up this frame.
+            self.accumulator %= self.step;
+            self.looping = false;
+            false
+        }
+    }
+}
+
+/// Same as Bevy's `FixedTimestep`, but with a step that can change once the project is loaded
+/// and a limit on how many ticks catch up in one frame.
+pub(crate) fn tick_criteria(time: Res<Time>, mut tick: ResMut<TickRate>) -> ShouldRun {
+    if tick.next_tick(time.delta_seconds_f64()) {
+        ShouldRun::YesAndCheckAgain
+    } else {
+        ShouldRun::No
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    /// Runs `frames` frames of `delta` seconds and returns how many ticks each one ran.
+    fn frames(tick: &mut TickRate, delta: f64, frames: usize) -> Vec<u32> {
+        (0..frames)
+            .map(|_| {
+                let mut ticks = 0;
+                while tick.next_tick(delta) {
+                    ticks += 1;
+                }
+                ticks
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ticks_do_not_depend_on_the_frame_rate() {
+        let mut tick = TickRate::default();
+        tick.start(30);
+        assert_eq!(frames(&mut tick, 1.0 / 60.0, 4), [0, 1, 0, 1]);
+        assert_eq!(frames(&mut tick, 1.0 / 15.0, 2), [2, 2]);
+        assert_eq!(tick.ticks(), 6);
+    }
+
+    #[test]
+    fn dropped_frames_catch_up_within_the_limit() {
+        let mut tick = TickRate::new(3);
+        tick.start(10);
+        assert_eq!(frames(&mut tick, 0.25, 1), [2]);
+        // A one second hitch only catches up three ticks; the rest is skipped.
+        assert_eq!(frames(&mut tick, 1.0, 1), [3]);
+        assert_eq!(frames(&mut tick, 0.1, 2), [1, 1]);
+    }
+
+    #[derive(Resource, Default)]
+    struct Ran(u32);
+
+    #[test]
+    fn runs_systems_once_per_tick() {
+        let mut app = App::new();
+        app.insert_resource(Time::default())
+            .insert_resource(TickRate::default())
+            .init_resource::<Ran>()
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(tick_criteria)
+                    .with_system(|mut ran: ResMut<Ran>| ran.0 += 1),
+            );
+        app.world.resource_mut::<TickRate>().start(20);
+
+        let mut now = Instant::now();
+        for _ in 0..10 {
+            app.world.resource_mut::<Time>().update_with_instant(now);
+            app.update();
+            now += Duration::from_millis(100);
+        }
+        // The first frame has no delta, the other nine run two ticks each.
+        assert_eq!(app.world.resource::<Ran>().0, 18);
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{blocks::Value, common::Ids, object::Object, variable::Variable};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum TraceValue {
+    Number(f32),
+    Bool(bool),
+    String(String),
+}
+impl From<&Value> for Option<TraceValue> {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Number(val) => Some(TraceValue::Number(*val)),
+            Value::Bool(val) => Some(TraceValue::Bool(*val)),
+            Value::String(val) => Some(TraceValue::String(val.clone())),
+            Value::Memory(_) => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct ObjectState {
+    pub(crate) id: String,
+    pub(crate) x: f32,
+    pub(crate) y: f32,
+    pub(crate) scale_x: f32,
+    pub(crate) scale_y: f32,
+    #[serde(default)]
+    pub(crate) rotation: f32,
+    #[serde(default)]
+    pub(crate) direction: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct VariableState {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) value: Option<TraceValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub(crate) struct State {
+    pub(crate) objects: Vec<ObjectState>,
+    pub(crate) variables: Vec<VariableState>,
+}
+impl State {
+    pub(crate) fn capture(
+        ids: &Ids,
+        objects: &Query<&mut Object>,
+        variables: &Query<&mut Variable>,
+    ) -> Self {
+        let mut state = State::default();
+        for (id, entity) in ids.iter() {
+            if let Ok(object) = objects.get(*entity) {
+                state.objects.push(ObjectState {
+                    id: id.0.clone(),
+                    x: object.translation.x,
+                    y: object.translation.y,
+                    scale_x: object.scale.x,
+                    scale_y: object.scale.y,
+                    rotation: object.rotation,
+                    direction: object.direction,
+                });
+            }
+            if let Ok(variable) = variables.get(*entity) {
+                state.variables.push(VariableState {
+                    id: id.0.clone(),
+                    name: variable.name.clone(),
+                    value: (&variable.value).into(),
+                });
+            }
+        }
+        state.objects.sort_by(|a, b| a.id.cmp(&b.id));
+        state.variables.sort_by(|a, b| a.id.cmp(&b.id));
+        state
+    }
+
+    fn delta(&self, after: &State) -> State {
+        State {
+            objects: after
+                .objects
+                .iter()
+                .filter(|object| !self.objects.contains(object))
+                .cloned()
+                .collect(),
+            variables: after
+                .variables
+                .iter()
+                .filter(|variable| !self.variables.contains(variable))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn apply(&mut self, delta: State) {
+        for object in delta.objects {
+            match self.objects.iter_mut().find(|o| o.id == object.id) {
+                Some(current) => *current = object,
+                None => self.objects.push(object),
+            }
+        }
+        for variable in delta.variables {
+            match self.variables.iter_mut().find(|v| v.id == variable.id) {
+                Some(current) => *current = variable,
+                None => self.variables.push(variable),
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct BlockTrace {
+    pub(crate) tick: u64,
+    pub(crate) runner: String,
+    pub(crate) depth: usize,
+    pub(crate) block_id: String,
+    pub(crate) block_type: String,
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    pub(crate) yielded: bool,
+    pub(crate) return_value: Option<TraceValue>,
+    pub(crate) delta: State,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TraceEntry {
+    Snapshot { tick: u64, state: State },
+    Block(BlockTrace),
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct TraceRecorder {
+    writer: Option<BufWriter<File>>,
+    tick: u64,
+    started: bool,
+}
+impl TraceRecorder {
+    /// Records to a new file at `path`, or nothing if it cannot be created.
+    pub(crate) fn create(path: &Path) -> Self {
+        let writer = File::create(path)
+            .map_err(|err| error!("cannot create trace file {}: {}", path.display(), err))
+            .ok()
+            .map(BufWriter::new);
+        TraceRecorder {
+            writer,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    pub(crate) fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub(crate) fn begin_tick(&mut self, state: impl FnOnce() -> State) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.tick += 1;
+        if !self.started {
+            self.started = true;
+            let tick = self.tick;
+            self.write(&TraceEntry::Snapshot {
+                tick,
+                state: state(),
+            });
+        }
+    }
+
+    pub(crate) fn record(&mut self, mut trace: BlockTrace, before: &State, after: &State) {
+        trace.delta = before.delta(after);
+        self.write(&TraceEntry::Block(trace));
+    }
+
+    pub(crate) fn flush(&mut self) {
+        if let Some(writer) = &mut self.writer {
+            if let Err(err) = writer.flush() {
+                error!("cannot write trace: {}", err);
+                self.writer = None;
+            }
+        }
+    }
+
+    fn write(&mut self, entry: &TraceEntry) {
+        let Some(writer) = &mut self.writer else {
+            return;
+        };
+        let result = serde_json::to_writer(&mut *writer, entry)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"));
+        if let Err(err) = result {
+            error!("cannot write trace: {}", err);
+            self.writer = None;
+        }
+    }
+}
+
+/// Reconstructs the state at the end of `tick` from a trace written by [`TraceRecorder`].
+pub(crate) fn replay(path: &str, tick: u64) -> anyhow::Result<State> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut state = State::default();
+    for line in reader.lines() {
+        match serde_json::from_str(&line?)? {
+            TraceEntry::Snapshot {
+                tick: snapshot_tick,
+                state: snapshot,
+            } if snapshot_tick <= tick => state = snapshot,
+            TraceEntry::Block(trace) if trace.tick <= tick => state.apply(trace.delta),
+            _ => break,
+        }
+    }
+    Ok(state)
+}
+
+/// Describes the objects and variables at the end of `tick` of a trace, one per line.
+pub fn replay_report(path: &str, tick: u64) -> anyhow::Result<String> {
+    let state = replay(path, tick)?;
+
+    let mut report = String::new();
+    writeln!(report, "tick {}", tick)?;
+    for object in &state.objects {
+        writeln!(
+            report,
+            "object {} x: {} y: {} scale: {}, {} rotation: {} direction: {}",
+            object.id,
+            object.x,
+            object.y,
+            object.scale_x,
+            object.scale_y,
+            object.rotation,
+            object.direction
+        )?;
+    }
+    for variable in &state.variables {
+        writeln!(
+            report,
+            "variable {} {} = {}",
+            variable.id,
+            variable.name,
+            serde_json::to_string(&variable.value)?
+        )?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use dotent::project::script::Param;
+
+    use super::*;
+    use crate::blocks::{
+        test_world::{raw_block, TestWorld},
+        Compiler,
+    };
+
+    #[test]
+    fn replay_rebuilds_the_state_of_each_tick() {
+        let path = std::env::temp_dir().join(format!("gitteol-trace-{}.jsonl", std::process::id()));
+        let mut world = TestWorld::new();
+        *world.resource_mut::<TraceRecorder>() = TraceRecorder::create(&path);
+        world.spawn_object("other", Vec2::new(5.0, 5.0));
+        world.spawn_variable("n", Value::Number(0.0));
+        world.spawn_variable("text", Value::String("a".to_string()));
+
+        let body = vec![
+            raw_block("move_x", vec![Param::Number(10.0)], vec![]),
+            raw_block(
+                "change_variable",
+                vec![Param::String("n".to_string()), Param::Number(1.0)],
+                vec![],
+            ),
+            raw_block(
+                "change_variable",
+                vec![
+                    Param::String("text".to_string()),
+                    Param::String("b".to_string()),
+                ],
+                vec![],
+            ),
+        ];
+        let repeat = raw_block("repeat_basic", vec![Param::Number(4.0)], vec![body]);
+        world.spawn_code(Compiler::compile(&[repeat]));
+
+        let mut states = Vec::new();
+        for _ in 0..4 {
+            world.tick();
+            states.push(world.capture());
+        }
+
+        let path = path.to_str().unwrap();
+        for (tick, state) in (1..).zip(&states) {
+            assert_eq!(&replay(path, tick).unwrap(), state, "tick {}", tick);
+        }
+        assert_eq!(states[3].objects[1].x, 40.0);
+        assert_eq!(
+            states[3].variables[1].value,
+            Some(TraceValue::String("abbbb".to_string()))
+        );
+        assert!(replay_report(path, 2)
+            .unwrap()
+            .contains("variable n n = 2.0"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
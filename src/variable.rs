@@ -1,9 +1,10 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::FocusPolicy};
 use dotent::project::variable::VariableType;
 
 use crate::{
     blocks::Value,
     common::{Id, Ids, LocalPos},
+    stage::StageTransform,
 };
 
 fn get_variable_color(variable_type: &VariableType) -> Color {
@@ -24,6 +25,30 @@ pub(crate) struct Variable {
     pub(crate) value: Value,
     pub(crate) visible: bool,
     pub(crate) pos: LocalPos,
+    pub(crate) min_value: f32,
+    pub(crate) max_value: f32,
+}
+impl Variable {
+    /// Where the slider handle sits between `min_value` and `max_value`, from 0 to 1.
+    fn slider_ratio(&self) -> f32 {
+        let value = self.value.as_number().unwrap_or(self.min_value);
+        let ratio = (value - self.min_value) / (self.max_value - self.min_value);
+        if ratio.is_finite() {
+            ratio.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The value at `ratio` of the slider, rounded like Entry does.
+    fn slider_value(&self, ratio: f32) -> f32 {
+        let value = self.min_value + (self.max_value - self.min_value) * ratio.clamp(0.0, 1.0);
+        if self.min_value.fract() == 0.0 && self.max_value.fract() == 0.0 {
+            value.round()
+        } else {
+            (value * 100.0).round() / 100.0
+        }
+    }
 }
 impl From<dotent::project::variable::Variable> for Variable {
     fn from(value: dotent::project::variable::Variable) -> Self {
@@ -34,10 +59,21 @@ impl From<dotent::project::variable::Variable> for Variable {
             value: value.value.into(),
             visible: value.visible,
             pos: LocalPos::new(value.x, value.y),
+            min_value: bound(value.min_value, 0.0),
+            max_value: bound(value.max_value, 100.0),
         }
     }
 }
 
+fn bound(value: Option<dotent::project::variable::Value>, default: f32) -> f32 {
+    value
+        .and_then(|value| Value::from(value).as_number().ok())
+        .unwrap_or(default)
+}
+
+const SLIDER_WIDTH: f32 = 100.0;
+const SLIDER_HANDLE_SIZE: f32 = 10.0;
+
 #[derive(Component)]
 pub(crate) enum VariableUiType {
     Container,
@@ -47,16 +83,29 @@ pub(crate) enum VariableUiType {
 #[derive(Component)]
 pub(crate) struct VariableUi(Entity);
 
+/// The node holding every watcher.
+#[derive(Component)]
+pub(crate) struct VariableUiRoot;
+
+/// The track of a slider watcher, which sets its variable when clicked or dragged.
+#[derive(Component)]
+pub(crate) struct SliderTrack(Entity);
+
+#[derive(Component)]
+pub(crate) struct SliderHandle(Entity);
+
 pub(crate) fn spawn_variable(
     commands: &mut Commands,
     font: Handle<Font>,
     variable: Variable,
+    stage: &StageTransform,
     ids: &mut Ids,
 ) -> Entity {
     let id = variable.id.clone();
     let name = variable.name.clone();
-    let position = variable.pos.to_variable_pos();
+    let position = variable.pos.to_variable_pos(stage);
     let color = get_variable_color(&variable.variable_type);
+    let is_slider = matches!(variable.variable_type, VariableType::Slide);
 
     let variable_entity = commands.spawn_empty().insert(variable).id();
 
@@ -66,6 +115,7 @@ pub(crate) fn spawn_variable(
         .spawn_empty()
         .insert(VariableUi(variable_entity))
         .insert(VariableUiType::Container)
+        .insert(Interaction::default())
         .insert(NodeBundle {
             style: Style {
                 position_type: PositionType::Absolute,
@@ -75,9 +125,11 @@ pub(crate) fn spawn_variable(
                     ..Default::default()
                 },
                 display: Display::Flex,
+                flex_direction: FlexDirection::Column,
                 ..Default::default()
             },
             background_color: color.into(),
+            focus_policy: FocusPolicy::Block,
             ..Default::default()
         })
         .with_children(|parent| {
@@ -122,6 +174,39 @@ pub(crate) fn spawn_variable(
                     ..Default::default()
                 })
                 .insert(VariableUiType::Text);
+
+            if is_slider {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(SLIDER_WIDTH), Val::Px(SLIDER_HANDLE_SIZE)),
+                            margin: UiRect::all(Val::Px(3.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::rgba(1.0, 1.0, 1.0, 0.5).into(),
+                        focus_policy: FocusPolicy::Block,
+                        ..Default::default()
+                    })
+                    .insert(Interaction::default())
+                    .insert(SliderTrack(variable_entity))
+                    .with_children(|track| {
+                        track
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    size: Size::new(
+                                        Val::Px(SLIDER_HANDLE_SIZE),
+                                        Val::Px(SLIDER_HANDLE_SIZE),
+                                    ),
+                                    ..Default::default()
+                                },
+                                background_color: Color::WHITE.into(),
+                                focus_policy: FocusPolicy::Pass,
+                                ..Default::default()
+                            })
+                            .insert(SliderHandle(variable_entity));
+                    });
+            }
         })
         .id()
 }
@@ -130,9 +215,13 @@ pub(crate) fn variable_ui_system(
     mut uis: Query<(&VariableUi, &mut Style, &Children)>,
     variables: Query<&Variable>,
     mut texts: Query<&mut Text, With<VariableUiType>>,
+    stage: Res<StageTransform>,
 ) {
     for (ui, mut style, children) in uis.iter_mut() {
         if let Ok(variable) = variables.get(ui.0) {
+            let position = variable.pos.to_variable_pos(&stage);
+            style.position.left = Val::Px(position.0);
+            style.position.top = Val::Px(position.1);
             if let Ok(mut text) = texts.get_mut(children[0]) {
                 text.sections[2].value = variable.value.as_string().unwrap();
             }
@@ -144,3 +233,132 @@ pub(crate) fn variable_ui_system(
         };
     }
 }
+
+pub(crate) fn slider_ui_system(
+    mut handles: Query<(&SliderHandle, &mut Style)>,
+    variables: Query<&Variable>,
+) {
+    for (handle, mut style) in &mut handles {
+        if let Ok(variable) = variables.get(handle.0) {
+            let left = variable.slider_ratio() * (SLIDER_WIDTH - SLIDER_HANDLE_SIZE);
+            style.position.left = Val::Px(left);
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) enum Drag {
+    #[default]
+    None,
+    /// Moving a watcher, grabbed `grab` stage units away from its position.
+    Watcher {
+        variable: Entity,
+        grab: Vec2,
+    },
+    Slider {
+        variable: Entity,
+        track: Entity,
+    },
+}
+
+/// Lets the user move watchers and drag slider handles, like Entry's stage.
+pub(crate) fn variable_drag_system(
+    mut drag: Local<Drag>,
+    buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    stage: Res<StageTransform>,
+    watchers: Query<(&VariableUi, &Interaction)>,
+    tracks: Query<(Entity, &SliderTrack, &Interaction, &Node, &GlobalTransform)>,
+    mut variables: Query<&mut Variable>,
+) {
+    if !buttons.pressed(MouseButton::Left) {
+        *drag = Drag::None;
+        return;
+    }
+    let Some(cursor) = windows
+        .get_primary()
+        .and_then(|window| window.cursor_position())
+    else {
+        return;
+    };
+    // Watcher positions are stored with y pointing down.
+    let pos = stage.cursor_to_stage(cursor) * Vec2::new(1.0, -1.0);
+
+    if buttons.just_pressed(MouseButton::Left) {
+        *drag = if let Some((track, slider, ..)) = tracks
+            .iter()
+            .find(|(_, _, interaction, ..)| **interaction == Interaction::Clicked)
+        {
+            Drag::Slider {
+                variable: slider.0,
+                track,
+            }
+        } else if let Some((ui, _)) = watchers
+            .iter()
+            .find(|(_, interaction)| **interaction == Interaction::Clicked)
+        {
+            match variables.get(ui.0) {
+                Ok(variable) => Drag::Watcher {
+                    variable: ui.0,
+                    grab: pos - variable.pos.as_vec2(),
+                },
+                Err(_) => Drag::None,
+            }
+        } else {
+            Drag::None
+        };
+    }
+
+    match *drag {
+        Drag::None => {}
+        Drag::Watcher { variable, grab } => {
+            if let Ok(mut variable) = variables.get_mut(variable) {
+                let pos = pos - grab;
+                variable.pos = LocalPos::new(pos.x, pos.y);
+            }
+        }
+        Drag::Slider { variable, track } => {
+            let (Ok(mut variable), Ok((_, _, _, node, transform))) =
+                (variables.get_mut(variable), tracks.get(track))
+            else {
+                return;
+            };
+            let left = transform.translation().x - node.size().x / 2.0;
+            let ratio = (cursor.x - left) / node.size().x;
+            let value = variable.slider_value(ratio);
+            variable.value = Value::Number(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slider(min_value: f32, max_value: f32, value: f32) -> Variable {
+        Variable {
+            id: Id::from_str("slider"),
+            variable_type: VariableType::Slide,
+            name: "slider".to_string(),
+            value: Value::Number(value),
+            visible: true,
+            pos: LocalPos::new(0.0, 0.0),
+            min_value,
+            max_value,
+        }
+    }
+
+    #[test]
+    fn slider_ratio_is_clamped() {
+        assert_eq!(slider(0.0, 100.0, 25.0).slider_ratio(), 0.25);
+        assert_eq!(slider(0.0, 100.0, 150.0).slider_ratio(), 1.0);
+        assert_eq!(slider(10.0, 10.0, 10.0).slider_ratio(), 0.0);
+    }
+
+    #[test]
+    fn slider_value_rounds_like_entry() {
+        assert_eq!(slider(0.0, 10.0, 0.0).slider_value(0.33), 3.0);
+        assert_eq!(slider(0.0, 1.5, 0.0).slider_value(0.333), 0.5);
+        assert_eq!(slider(-5.0, 5.0, 0.0).slider_value(-1.0), -5.0);
+    }
+}
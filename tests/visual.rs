@@ -0,0 +1,138 @@
+//! Renders the projects in `tests/fixtures` headless and compares the last frame with the
+//! reference PNG next to each project.
+//!
+//! They need a GPU or a software renderer such as Mesa's llvmpipe, so they only run with
+//! `cargo test --test visual -- --ignored`. Set `GITTEOL_BLESS=1` to write the rendered frames as
+//! the new references.
+
+use std::{path::PathBuf, sync::Mutex};
+
+use bevy::{
+    log::LogPlugin,
+    prelude::*,
+    render::settings::{Backends, WgpuSettings},
+    winit::WinitPlugin,
+};
+use gitteol::{GitteolConfig, GitteolPlugin, HeadlessPlugin};
+use image::RgbaImage;
+
+const FIXTURES: &str = "tests/fixtures";
+
+/// pixelmatch's default: how far apart two colors may be in YIQ space, from 0 to 1.
+const COLOR_THRESHOLD: f32 = 0.1;
+/// Share of pixels which may differ, for anti-aliasing differences between renderers.
+const PIXEL_TOLERANCE: f32 = 0.005;
+
+/// Only one app renders at a time.
+static RENDER: Mutex<()> = Mutex::new(());
+
+fn render(name: &str, ticks: u64) -> RgbaImage {
+    let capture_dir = std::env::temp_dir().join(format!("gitteol-visual-{}", name));
+    let _ = std::fs::remove_dir_all(&capture_dir);
+    std::fs::create_dir_all(&capture_dir).unwrap();
+
+    let config = GitteolConfig {
+        project_path: format!("{}.ent", name),
+        seed: Some(0),
+        capture_dir: capture_dir.clone(),
+        capture_ticks: Some(ticks),
+        ..Default::default()
+    };
+    let window_size = config.window_size();
+    App::new()
+        .insert_resource(WgpuSettings {
+            backends: Some(
+                wgpu::util::backend_bits_from_env().unwrap_or(Backends::PRIMARY | Backends::GL),
+            ),
+            ..Default::default()
+        })
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    add_primary_window: false,
+                    exit_on_all_closed: false,
+                    ..Default::default()
+                })
+                .set(AssetPlugin {
+                    asset_folder: FIXTURES.to_string(),
+                    ..Default::default()
+                })
+                .disable::<WinitPlugin>()
+                .disable::<LogPlugin>(),
+        )
+        .add_plugin(HeadlessPlugin { window_size })
+        .add_plugin(GitteolPlugin { config })
+        .run();
+
+    let last_frame = std::fs::read_dir(&capture_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .max()
+        .unwrap_or_else(|| panic!("{} rendered no frames", name));
+    image::open(last_frame).unwrap().to_rgba8()
+}
+
+/// Squared distance between two colors in YIQ space, as in pixelmatch.
+fn color_delta(a: &image::Rgba<u8>, b: &image::Rgba<u8>) -> f32 {
+    let yiq = |p: &image::Rgba<u8>| {
+        let [r, g, b] = [p[0], p[1], p[2]].map(|c| c as f32 * p[3] as f32 / 255.0);
+        [
+            r * 0.299 + g * 0.587 + b * 0.114,
+            r * 0.596 - g * 0.274 - b * 0.322,
+            r * 0.211 - g * 0.523 + b * 0.312,
+        ]
+    };
+    let ([y1, i1, q1], [y2, i2, q2]) = (yiq(a), yiq(b));
+    0.5053 * (y1 - y2).powi(2) + 0.299 * (i1 - i2).powi(2) + 0.1957 * (q1 - q2).powi(2)
+}
+
+fn check(name: &str, ticks: u64) {
+    let _guard = RENDER.lock().unwrap_or_else(|err| err.into_inner());
+    let actual = render(name, ticks);
+    let reference_path = PathBuf::from(FIXTURES).join(format!("{}.png", name));
+
+    if std::env::var_os("GITTEOL_BLESS").is_some() {
+        actual.save(&reference_path).unwrap();
+        return;
+    }
+
+    let reference = image::open(&reference_path).unwrap().to_rgba8();
+    assert_eq!(actual.dimensions(), reference.dimensions(), "{}", name);
+
+    let max_delta = 35215.0 * COLOR_THRESHOLD * COLOR_THRESHOLD;
+    let different = actual
+        .pixels()
+        .zip(reference.pixels())
+        .filter(|(a, b)| color_delta(a, b) > max_delta)
+        .count();
+    let ratio = different as f32 / (actual.width() * actual.height()) as f32;
+    if ratio > PIXEL_TOLERANCE {
+        let actual_path = std::env::temp_dir().join(format!("gitteol-visual-{}.png", name));
+        actual.save(&actual_path).unwrap();
+        panic!(
+            "{}: {:.2}% of pixels differ from {}, rendered frame saved to {}",
+            name,
+            ratio * 100.0,
+            reference_path.display(),
+            actual_path.display()
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs a GPU or a software renderer"]
+fn sprite_placement() {
+    check("sprite_placement", 10);
+}
+
+#[test]
+#[ignore = "needs a GPU or a software renderer"]
+fn sprite_scale() {
+    check("sprite_scale", 10);
+}
+
+#[test]
+#[ignore = "needs a GPU or a software renderer"]
+fn variable_watchers() {
+    check("variable_watchers", 10);
+}